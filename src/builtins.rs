@@ -0,0 +1,228 @@
+use std::sync::Arc;
+
+use crate::*;
+
+// What the built-in commands are wired up to. The binary uses the process's
+// terminal and working directory, while the test harness swaps in a
+// CaptureHostIo and a seeded random source, but both register the same commands.
+pub struct Builtins {
+    pub io: Arc<dyn HostIo>,
+    pub cwd: Arc<WorkingDir>,
+    pub warnings: Warnings,
+    pub random: Arc<RandomSource>,
+    pub permissions: Arc<Permissions>,
+}
+
+impl Builtins {
+    // A registry with every built-in command. Commands that take a block, like
+    // timeit, aren't included since there's no block to give them.
+    pub fn registry(&self) -> CommandRegistry {
+        let io = &self.io;
+        let cwd = &self.cwd;
+        let warnings = &self.warnings;
+        let random = &self.random;
+        let permissions = &self.permissions;
+
+        let mut map = CommandRegistry::new();
+        map.insert(CountCommand::signature(), CountCommand);
+        map.insert(TakeCommand::signature(), TakeCommand);
+        map.insert(SumCommand::signature(), SumCommand);
+        map.insert(AppendCommand::signature(), AppendCommand);
+        map.insert(PrependCommand::signature(), PrependCommand);
+        map.insert(WhereCommand::signature(), WhereCommand);
+        map.insert(LengthCommand::signature(), LengthCommand);
+        map.insert(ParEachCommand::signature(), ParEachCommand);
+        map.insert(InspectCommand::signature(), InspectCommand::new(io.clone()));
+        map.insert(ContainsCommand::signature(), ContainsCommand);
+        map.insert(
+            OpenCommand::signature(),
+            permissions.guard(
+                Capability::Filesystem,
+                "open",
+                warnings,
+                OpenCommand::new(cwd.clone()),
+            ),
+        );
+        map.insert(StrLengthCommand::signature(), StrLengthCommand);
+        map.insert(InputCommand::signature(), InputCommand::new(io.clone()));
+        map.insert(PrintCommand::signature(), PrintCommand::new(io.clone()));
+        map.insert(
+            ClipCopyCommand::signature(),
+            ClipCopyCommand::new(io.clone()),
+        );
+        map.insert(
+            ClipPasteCommand::signature(),
+            ClipPasteCommand::new(io.clone()),
+        );
+        map.insert(NotifyCommand::signature(), NotifyCommand::new(io.clone()));
+        map.insert(EchoCommand::signature(), EchoCommand);
+        map.insert(LinesCommand::signature(), LinesCommand);
+        map.insert(
+            SplitRowCommand::signature(),
+            SplitRowCommand::new(warnings.clone()),
+        );
+        map.insert(FindCommand::signature(), FindCommand::new(warnings.clone()));
+        map.insert(DropCommand::signature(), DropCommand::new(warnings.clone()));
+        map.insert(HashMd5Command::signature(), HashMd5Command);
+        map.insert(HashSha256Command::signature(), HashSha256Command);
+        map.insert(EncodeBase64Command::signature(), EncodeBase64Command);
+        map.insert(EncodeHexCommand::signature(), EncodeHexCommand);
+        map.insert(DecodeBase64Command::signature(), DecodeBase64Command);
+        map.insert(DecodeHexCommand::signature(), DecodeHexCommand);
+        map.insert(
+            SeqDateCommand::signature(),
+            SeqDateCommand::new(warnings.clone()),
+        );
+        map.insert(CharCommand::signature(), CharCommand::new(warnings.clone()));
+        map.insert(IntoDurationCommand::signature(), IntoDurationCommand);
+        map.insert(FormatDurationCommand::signature(), FormatDurationCommand);
+        map.insert(
+            BitsAndCommand::signature(),
+            BitsAndCommand::new(warnings.clone()),
+        );
+        map.insert(
+            BitsOrCommand::signature(),
+            BitsOrCommand::new(warnings.clone()),
+        );
+        map.insert(
+            BitsXorCommand::signature(),
+            BitsXorCommand::new(warnings.clone()),
+        );
+        map.insert(BitsNotCommand::signature(), BitsNotCommand);
+        map.insert(
+            BitsShlCommand::signature(),
+            BitsShlCommand::new(warnings.clone()),
+        );
+        map.insert(
+            BitsShrCommand::signature(),
+            BitsShrCommand::new(warnings.clone()),
+        );
+        map.insert(
+            BitsRolCommand::signature(),
+            BitsRolCommand::new(warnings.clone()),
+        );
+        map.insert(
+            BitsRorCommand::signature(),
+            BitsRorCommand::new(warnings.clone()),
+        );
+        map.insert(SortCommand::signature(), SortCommand::new(warnings.clone()));
+        map.insert(UniqCommand::signature(), UniqCommand);
+        map.insert(
+            CdCommand::signature(),
+            CdCommand::new(cwd.clone(), warnings.clone()),
+        );
+        map.insert(PwdCommand::signature(), PwdCommand::new(cwd.clone()));
+        map.insert(
+            MkdirCommand::signature(),
+            permissions.guard(
+                Capability::Filesystem,
+                "mkdir",
+                warnings,
+                MkdirCommand::new(cwd.clone(), warnings.clone()),
+            ),
+        );
+        map.insert(
+            CpCommand::signature(),
+            permissions.guard(
+                Capability::Filesystem,
+                "cp",
+                warnings,
+                CpCommand::new(cwd.clone(), warnings.clone()),
+            ),
+        );
+        map.insert(
+            MvCommand::signature(),
+            permissions.guard(
+                Capability::Filesystem,
+                "mv",
+                warnings,
+                MvCommand::new(cwd.clone(), warnings.clone()),
+            ),
+        );
+        map.insert(
+            RmCommand::signature(),
+            permissions.guard(
+                Capability::Filesystem,
+                "rm",
+                warnings,
+                RmCommand::new(cwd.clone(), warnings.clone()),
+            ),
+        );
+        map.insert(
+            TouchCommand::signature(),
+            permissions.guard(
+                Capability::Filesystem,
+                "touch",
+                warnings,
+                TouchCommand::new(cwd.clone(), warnings.clone()),
+            ),
+        );
+        map.insert(
+            DuCommand::signature(),
+            permissions.guard(
+                Capability::Filesystem,
+                "du",
+                warnings,
+                DuCommand::new(cwd.clone(), warnings.clone()),
+            ),
+        );
+        map.insert(
+            PathJoinCommand::signature(),
+            PathJoinCommand::new(warnings.clone()),
+        );
+        map.insert(PathDirnameCommand::signature(), PathDirnameCommand);
+        map.insert(PathBasenameCommand::signature(), PathBasenameCommand);
+        map.insert(
+            PathExpandCommand::signature(),
+            PathExpandCommand::new(cwd.clone()),
+        );
+        map.insert(
+            PathExistsCommand::signature(),
+            PathExistsCommand::new(cwd.clone()),
+        );
+        map.insert(
+            PathTypeCommand::signature(),
+            PathTypeCommand::new(cwd.clone()),
+        );
+        map.insert(ToNuonCommand::signature(), ToNuonCommand);
+        map.insert(FromNuonCommand::signature(), FromNuonCommand);
+        #[cfg(feature = "msgpack")]
+        {
+            map.insert(ToMsgpackCommand::signature(), ToMsgpackCommand);
+            map.insert(FromMsgpackCommand::signature(), FromMsgpackCommand);
+        }
+        map.insert(
+            RandomIntCommand::signature(),
+            RandomIntCommand::new(random.clone(), warnings.clone()),
+        );
+        map.insert(
+            RandomUuidCommand::signature(),
+            RandomUuidCommand::new(random.clone()),
+        );
+        map.insert(
+            RandomCharsCommand::signature(),
+            RandomCharsCommand::new(random.clone(), warnings.clone()),
+        );
+        map.insert(
+            RandomBoolCommand::signature(),
+            RandomBoolCommand::new(random.clone()),
+        );
+        map.insert(
+            RandomDiceCommand::signature(),
+            RandomDiceCommand::new(random.clone(), warnings.clone()),
+        );
+        map.insert(
+            StdinCommand::signature(),
+            StdinCommand::new(warnings.clone()),
+        );
+
+        // Help and which describe the commands registered before them, so they
+        // come last
+        let help = HelpCommand::new(&map);
+        map.insert(HelpCommand::signature(), help);
+        let which = WhichCommand::new(&map);
+        map.insert(WhichCommand::signature(), which);
+
+        map
+    }
+}
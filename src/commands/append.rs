@@ -2,6 +2,19 @@ use crate::*;
 
 pub struct AppendCommand;
 
+impl AppendCommand {
+    pub fn signature() -> Signature {
+        Signature::build("append")
+            .desc("Adds a value to the end of the stream")
+            .required("value", ArgType::Any, "the value to add")
+            .example(
+                "Add a value to the end",
+                "echo 1 2 | append 3",
+                vec![Value::SmallInt(1), Value::SmallInt(2), Value::SmallInt(3)],
+            )
+    }
+}
+
 impl PipelineElement for AppendCommand {
     fn start(&self, args: CommandArgs) -> ValueIterator {
         let step = std::iter::once(args.args[0].clone());
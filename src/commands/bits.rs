@@ -0,0 +1,276 @@
+use std::convert::TryFrom;
+
+use crate::*;
+
+// Bitwise commands, for working with flags and binary protocol data. They work
+// on the 64 bits of each integer in the input, whether it's a SmallInt or a
+// BigInt small enough to fit. Anything else is dropped.
+
+pub struct BitsAndCommand {
+    warnings: Warnings,
+}
+
+impl BitsAndCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("bits and")
+            .desc("Bitwise ands each integer with the target")
+            .required("target", ArgType::Int, "the integer to and with")
+            .example(
+                "Keep the low bits",
+                "echo 14 | bits and 3",
+                vec![Value::SmallInt(2)],
+            )
+    }
+}
+
+impl PipelineElement for BitsAndCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        match operand(&Self::signature(), args.args, "target", &self.warnings) {
+            Some(target) => map_ints(args.input, move |x| Some(x & target)),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+pub struct BitsOrCommand {
+    warnings: Warnings,
+}
+
+impl BitsOrCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("bits or")
+            .desc("Bitwise ors each integer with the target")
+            .required("target", ArgType::Int, "the integer to or with")
+            .example("Set a flag", "echo 8 | bits or 1", vec![Value::SmallInt(9)])
+    }
+}
+
+impl PipelineElement for BitsOrCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        match operand(&Self::signature(), args.args, "target", &self.warnings) {
+            Some(target) => map_ints(args.input, move |x| Some(x | target)),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+pub struct BitsXorCommand {
+    warnings: Warnings,
+}
+
+impl BitsXorCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("bits xor")
+            .desc("Bitwise xors each integer with the target")
+            .required("target", ArgType::Int, "the integer to xor with")
+            .example(
+                "Flip the low bits",
+                "echo 12 | bits xor 5",
+                vec![Value::SmallInt(9)],
+            )
+    }
+}
+
+impl PipelineElement for BitsXorCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        match operand(&Self::signature(), args.args, "target", &self.warnings) {
+            Some(target) => map_ints(args.input, move |x| Some(x ^ target)),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+pub struct BitsNotCommand;
+
+impl BitsNotCommand {
+    pub fn signature() -> Signature {
+        Signature::build("bits not")
+            .desc("Flips every bit of each integer")
+            .example("Flip zero", "echo 0 | bits not", vec![Value::SmallInt(-1)])
+    }
+}
+
+impl PipelineElement for BitsNotCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        map_ints(args.input, |x| Some(!x))
+    }
+}
+
+// Shifts left, dropping integers that don't fit in 64 bits once shifted, rather
+// than letting their top bits fall off
+pub struct BitsShlCommand {
+    warnings: Warnings,
+}
+
+impl BitsShlCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("bits shl")
+            .desc("Shifts each integer left")
+            .required("bits", ArgType::Int, "how many bits to shift by")
+            .example(
+                "Shift left",
+                "echo 3 | bits shl 4",
+                vec![Value::SmallInt(48)],
+            )
+    }
+}
+
+impl PipelineElement for BitsShlCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        match shift(&Self::signature(), args.args, &self.warnings) {
+            Some(bits) => map_ints(args.input, move |x| {
+                x.checked_shl(bits).filter(|shifted| shifted >> bits == x)
+            }),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+// Shifts right, keeping the sign like an arithmetic shift
+pub struct BitsShrCommand {
+    warnings: Warnings,
+}
+
+impl BitsShrCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("bits shr")
+            .desc("Shifts each integer right")
+            .required("bits", ArgType::Int, "how many bits to shift by")
+            .example(
+                "Shift right",
+                "echo 48 | bits shr 4",
+                vec![Value::SmallInt(3)],
+            )
+    }
+}
+
+impl PipelineElement for BitsShrCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        match shift(&Self::signature(), args.args, &self.warnings) {
+            Some(bits) => map_ints(args.input, move |x| x.checked_shr(bits)),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+pub struct BitsRolCommand {
+    warnings: Warnings,
+}
+
+impl BitsRolCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("bits rol")
+            .desc("Rotates the bits of each integer left")
+            .required("bits", ArgType::Int, "how many bits to rotate by")
+            .example(
+                "Rotate the top bits around",
+                "echo 3 | bits rol 63",
+                vec![Value::SmallInt(i64::MIN + 1)],
+            )
+    }
+}
+
+impl PipelineElement for BitsRolCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        match shift(&Self::signature(), args.args, &self.warnings) {
+            Some(bits) => map_ints(args.input, move |x| Some(x.rotate_left(bits))),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+pub struct BitsRorCommand {
+    warnings: Warnings,
+}
+
+impl BitsRorCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("bits ror")
+            .desc("Rotates the bits of each integer right")
+            .required("bits", ArgType::Int, "how many bits to rotate by")
+            .example(
+                "Rotate the bottom bit around",
+                "echo 3 | bits ror 1",
+                vec![Value::SmallInt(i64::MIN + 1)],
+            )
+    }
+}
+
+impl PipelineElement for BitsRorCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        match shift(&Self::signature(), args.args, &self.warnings) {
+            Some(bits) => map_ints(args.input, move |x| Some(x.rotate_right(bits))),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+fn operand(
+    signature: &Signature,
+    args: Vec<Value>,
+    name: &str,
+    warnings: &Warnings,
+) -> Option<i64> {
+    match EvaluatedArgs::new(signature, args) {
+        Ok(evaluated) => match evaluated.get(name) {
+            Some(Value::SmallInt(x)) => Some(*x),
+            _ => None,
+        },
+        Err(err) => {
+            warnings.arg_error(&signature.name, err);
+            None
+        }
+    }
+}
+
+// Shift and rotate amounts can't be negative
+fn shift(signature: &Signature, args: Vec<Value>, warnings: &Warnings) -> Option<u32> {
+    let bits = operand(signature, args, "bits", warnings)?;
+    match u32::try_from(bits) {
+        Ok(bits) => Some(bits),
+        Err(_) => {
+            warnings.arg_error(&signature.name, "`bits` can't be negative");
+            None
+        }
+    }
+}
+
+fn map_ints(
+    input: ValueIterator,
+    f: impl Fn(i64) -> Option<i64> + Send + Sync + 'static,
+) -> ValueIterator {
+    Box::new(input.filter_map(move |x| match x {
+        Value::SmallInt(x) => f(x).map(Value::SmallInt),
+        // Commands like `length` output BigInts even for small counts
+        Value::BigInt(x) => i64::try_from(&x).ok().and_then(&f).map(Value::SmallInt),
+        _ => None,
+    }))
+}
@@ -0,0 +1,49 @@
+use crate::*;
+
+use std::sync::Arc;
+
+// Hands each value to the debugger before passing it on, pausing the pipeline
+// until the debugger lets it continue. Bad arguments are warned about, but the
+// values still go to the debugger, just without a label.
+pub struct BreakpointCommand {
+    debugger: Arc<dyn DebuggerHook>,
+    warnings: Warnings,
+}
+
+impl BreakpointCommand {
+    pub fn new(debugger: Arc<dyn DebuggerHook>, warnings: Warnings) -> Self {
+        Self { debugger, warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("breakpoint")
+            .desc("Pauses on each value and hands it to the debugger")
+            .optional_with_default(
+                "label",
+                ArgType::String,
+                Value::String(String::new()),
+                "a name for the breakpoint, passed to the debugger",
+            )
+    }
+}
+
+impl PipelineElement for BreakpointCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let label = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => match evaluated.get("label") {
+                Some(Value::String(s)) => s.clone(),
+                _ => String::new(),
+            },
+            Err(err) => {
+                self.warnings.arg_error("breakpoint", err);
+                String::new()
+            }
+        };
+
+        let debugger = self.debugger.clone();
+        Box::new(
+            args.input
+                .inspect(move |x| debugger.on_breakpoint(&label, x)),
+        )
+    }
+}
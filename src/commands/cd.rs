@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use crate::deferred_adapter::DeferredIterator;
+use crate::working_dir::home_dir;
+use crate::*;
+
+// Changes the working directory, going home if no path is given. A path that
+// isn't a directory leaves the working directory where it was, with a warning.
+pub struct CdCommand {
+    cwd: Arc<WorkingDir>,
+    warnings: Warnings,
+}
+
+impl CdCommand {
+    pub fn new(cwd: Arc<WorkingDir>, warnings: Warnings) -> Self {
+        Self { cwd, warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("cd")
+            .side_effects()
+            .desc("Changes the working directory")
+            .optional("path", ArgType::String, "the directory to change to")
+    }
+}
+
+impl PipelineElement for CdCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let target = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => match evaluated.get("path") {
+                Some(Value::String(path)) => Some(self.cwd.resolve(path)),
+                _ => home_dir(),
+            },
+            Err(err) => {
+                self.warnings.arg_error("cd", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        // The directory only changes once the pipeline runs
+        let cwd = self.cwd.clone();
+        let warnings = self.warnings.clone();
+        Box::new(DeferredIterator::new(move || {
+            let target = match target {
+                Some(target) => target,
+                None => {
+                    warnings.arg_error("cd", "there's no home directory to go to");
+                    return Box::new(std::iter::empty());
+                }
+            };
+
+            // Canonicalizing tidies away any `..` and checks the path exists
+            match target.canonicalize() {
+                Ok(dir) if dir.is_dir() => cwd.set(dir),
+                _ => warnings.arg_error("cd", format!("`{}` isn't a directory", target.display())),
+            }
+
+            Box::new(std::iter::empty())
+        }))
+    }
+}
+
+// Outputs the working directory
+pub struct PwdCommand {
+    cwd: Arc<WorkingDir>,
+}
+
+impl PwdCommand {
+    pub fn new(cwd: Arc<WorkingDir>) -> Self {
+        Self { cwd }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("pwd").desc("Outputs the working directory")
+    }
+}
+
+impl PipelineElement for PwdCommand {
+    fn start(&self, _: CommandArgs) -> ValueIterator {
+        let path = self.cwd.get().to_string_lossy().to_string();
+
+        Box::new(std::iter::once(Value::String(path)))
+    }
+}
@@ -0,0 +1,117 @@
+use crate::*;
+
+// Named characters that are awkward to type in a pipeline
+const CHARS: &[(&str, &str)] = &[
+    ("newline", "\n"),
+    ("carriage_return", "\r"),
+    ("crlf", "\r\n"),
+    ("tab", "\t"),
+    ("space", " "),
+    ("nbsp", "\u{a0}"),
+    ("null", "\0"),
+    ("escape", "\u{1b}"),
+    ("bell", "\u{7}"),
+    ("backspace", "\u{8}"),
+    ("single_quote", "'"),
+    ("double_quote", "\""),
+    ("backtick", "`"),
+    ("pipe", "|"),
+    ("left_brace", "{"),
+    ("right_brace", "}"),
+    ("left_bracket", "["),
+    ("right_bracket", "]"),
+    ("left_paren", "("),
+    ("right_paren", ")"),
+    ("path_sep", std::path::MAIN_SEPARATOR_STR),
+    ("bullet", "\u{2022}"),
+    ("middle_dot", "\u{b7}"),
+    ("ellipsis", "\u{2026}"),
+    ("check", "\u{2714}"),
+    ("cross", "\u{2716}"),
+    ("heart", "\u{2764}"),
+    ("star", "\u{2b50}"),
+    ("smile", "\u{1f604}"),
+    ("thumbs_up", "\u{1f44d}"),
+    ("sparkles", "\u{2728}"),
+    ("rocket", "\u{1f680}"),
+];
+
+// Outputs a special character by name, like `char newline`, or by code point
+// with --unicode. With --list, outputs the known names instead.
+pub struct CharCommand {
+    warnings: Warnings,
+}
+
+impl CharCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("char")
+            .desc("Outputs a named special character")
+            .optional("name", ArgType::String, "the name of the character")
+            .switch("list", Some('l'), "list the known names")
+            .switch(
+                "unicode",
+                Some('u'),
+                "treat the name as a hex code point, like 1f680",
+            )
+            .example("Output a tab", "char tab", vec![Value::String("\t".into())])
+            .example(
+                "Output a character by code point",
+                "char -u 41",
+                vec![Value::String("A".into())],
+            )
+    }
+}
+
+impl PipelineElement for CharCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.warnings.arg_error("char", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        if evaluated.has("list") {
+            return Box::new(
+                CHARS
+                    .iter()
+                    .map(|(name, _)| Value::String(name.to_string())),
+            );
+        }
+
+        let name = match evaluated.get("name") {
+            Some(Value::String(name)) => name,
+            _ => {
+                self.warnings
+                    .arg_error("char", ArgError::MissingPositional("name".into()));
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let found = if evaluated.has("unicode") {
+            u32::from_str_radix(name.trim_start_matches("U+"), 16)
+                .ok()
+                .and_then(std::char::from_u32)
+                .map(|x| x.to_string())
+        } else {
+            CHARS
+                .iter()
+                .find(|(x, _)| x == name)
+                .map(|(_, x)| x.to_string())
+        };
+
+        match found {
+            Some(x) => Box::new(std::iter::once(Value::String(x))),
+            None => {
+                self.warnings
+                    .arg_error("char", format!("no character named `{}`", name));
+                Box::new(std::iter::empty())
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::deferred_adapter::DeferredIterator;
+use crate::*;
+
+use std::sync::Arc;
+
+// Copies the input to the host's clipboard, one value per line. Nothing is passed
+// on down the pipeline.
+pub struct ClipCopyCommand {
+    io: Arc<dyn HostIo>,
+}
+
+impl ClipCopyCommand {
+    pub fn new(io: Arc<dyn HostIo>) -> Self {
+        Self { io }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("clip copy")
+            .side_effects()
+            .desc("Copies the input to the clipboard")
+            .switch("nuon", Some('n'), "copy values as NUON rather than as text")
+            .example(
+                "Copy values, then paste them back",
+                "echo a b | clip copy | clip paste",
+                vec![Value::String("a\nb".into())],
+            )
+    }
+}
+
+impl PipelineElement for ClipCopyCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let nuon = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated.has("nuon"),
+            Err(err) => {
+                self.io.write_err(&format!("clip copy: {}\n", err));
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        Box::new(ClipCopyIterator {
+            input: args.input,
+            io: self.io.clone(),
+            nuon,
+            done: false,
+        })
+    }
+}
+
+struct ClipCopyIterator {
+    input: ValueIterator,
+    io: Arc<dyn HostIo>,
+    nuon: bool,
+    done: bool,
+}
+
+impl Iterator for ClipCopyIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        self.done = true;
+        let mut lines = vec![];
+        for value in &mut self.input {
+            let line = if self.nuon {
+                to_nuon(&value)
+            } else {
+                Some(format_value(&value))
+            };
+            lines.extend(line);
+        }
+
+        if !self.io.set_clipboard(&lines.join("\n")) {
+            self.io
+                .write_err("clip copy: there's no clipboard to copy to\n");
+        }
+
+        None
+    }
+}
+
+// Outputs what's on the host's clipboard as a string. Anything coming in is run
+// first, so `... | clip copy | clip paste` pastes what was just copied.
+pub struct ClipPasteCommand {
+    io: Arc<dyn HostIo>,
+}
+
+impl ClipPasteCommand {
+    pub fn new(io: Arc<dyn HostIo>) -> Self {
+        Self { io }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("clip paste").desc("Outputs what's on the clipboard")
+    }
+}
+
+impl PipelineElement for ClipPasteCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let io = self.io.clone();
+        let input = args.input;
+
+        Box::new(DeferredIterator::new(move || {
+            input.for_each(drop);
+
+            match io.get_clipboard() {
+                Some(text) => Box::new(std::iter::once(Value::String(text))),
+                None => Box::new(std::iter::empty()),
+            }
+        }))
+    }
+}
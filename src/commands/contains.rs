@@ -2,6 +2,19 @@ use crate::*;
 
 pub struct ContainsCommand;
 
+impl ContainsCommand {
+    pub fn signature() -> Signature {
+        Signature::build("contains")
+            .desc("Outputs the first value equal to the given one, if there is one")
+            .required("value", ArgType::Any, "the value to look for")
+            .example(
+                "Find a value in the stream",
+                "count | contains 3",
+                vec![Value::SmallInt(3)],
+            )
+    }
+}
+
 impl PipelineElement for ContainsCommand {
     fn start(&self, args: CommandArgs) -> ValueIterator {
         let item = args.args[0].clone();
@@ -3,6 +3,18 @@ use crate::*;
 // Counts from 0 to the highest it can
 pub struct CountCommand;
 
+impl CountCommand {
+    pub fn signature() -> Signature {
+        Signature::build("count")
+            .desc("Counts up from 0, forever")
+            .example(
+                "Count up to 2",
+                "count | take 3",
+                vec![Value::SmallInt(0), Value::SmallInt(1), Value::SmallInt(2)],
+            )
+    }
+}
+
 impl PipelineElement for CountCommand {
     fn start(&self, _: CommandArgs) -> ValueIterator {
         Box::new(CountIterator {
@@ -0,0 +1,75 @@
+use crate::*;
+use std::collections::VecDeque;
+
+// Drops the last values of the stream. The stream is only held back by as many
+// values as are being dropped, so this still works on long streams.
+pub struct DropCommand {
+    warnings: Warnings,
+}
+
+impl DropCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("drop")
+            .desc("Removes the last values of the stream")
+            .optional_with_default(
+                "n",
+                ArgType::Int,
+                Value::SmallInt(1),
+                "how many values to remove",
+            )
+            .example(
+                "Drop the last two values",
+                "echo 1 2 3 | drop 2",
+                vec![Value::SmallInt(1)],
+            )
+    }
+}
+
+impl PipelineElement for DropCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let n = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => match evaluated.get("n") {
+                Some(Value::SmallInt(n)) if *n >= 0 => *n as usize,
+                _ => {
+                    self.warnings.arg_error("drop", "`n` can't be negative");
+                    return Box::new(std::iter::empty());
+                }
+            },
+            Err(err) => {
+                self.warnings.arg_error("drop", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        Box::new(DropIterator {
+            input: args.input,
+            n,
+            // n can be anything up to i64::MAX, so the queue grows as values
+            // come in rather than being sized for n up front
+            held: VecDeque::new(),
+        })
+    }
+}
+
+struct DropIterator {
+    input: ValueIterator,
+    n: usize,
+    held: VecDeque<Value>,
+}
+
+impl Iterator for DropIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.held.len() <= self.n {
+            let next = self.input.next()?;
+            self.held.push_back(next);
+        }
+
+        self.held.pop_front()
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fs::ReadDir;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::*;
+
+// Outputs how much space each directory takes up, like `du -b`: the size in
+// bytes as an int, followed by the directory's path as a string, so `du -d 0 |
+// sum` adds up the roots' sizes. Directories are output once
+// everything in them has been counted, so sizes stream out while the walk goes
+// on, and dropping the stream stops the walk. Symlinks are counted as
+// themselves rather than followed.
+pub struct DuCommand {
+    cwd: Arc<WorkingDir>,
+    warnings: Warnings,
+}
+
+impl DuCommand {
+    pub fn new(cwd: Arc<WorkingDir>, warnings: Warnings) -> Self {
+        Self { cwd, warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("du")
+            .desc("Outputs the disk usage of directories")
+            .rest("paths", ArgType::String, "what to measure (. by default)")
+            .named(
+                "max-depth",
+                Some('d'),
+                ArgType::Int,
+                "only output directories this deep or less",
+            )
+    }
+}
+
+impl PipelineElement for DuCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.warnings.arg_error("du", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let max_depth = match evaluated.get("max-depth") {
+            Some(Value::SmallInt(n)) if *n >= 0 => Some(*n as usize),
+            Some(_) => {
+                self.warnings
+                    .arg_error("du", "`--max-depth` can't be negative");
+                return Box::new(std::iter::empty());
+            }
+            None => None,
+        };
+
+        let mut roots: VecDeque<_> = evaluated
+            .rest()
+            .iter()
+            .filter_map(|x| match x {
+                Value::String(s) => Some(self.cwd.resolve(s)),
+                _ => None,
+            })
+            .collect();
+        if roots.is_empty() {
+            roots.push_back(self.cwd.get());
+        }
+
+        Box::new(DuIterator {
+            roots,
+            stack: vec![],
+            max_depth,
+            path: None,
+        })
+    }
+}
+
+// A directory partway through being counted
+struct Frame {
+    path: PathBuf,
+    depth: usize,
+    entries: ReadDir,
+    total: u64,
+}
+
+struct DuIterator {
+    roots: VecDeque<PathBuf>,
+    stack: Vec<Frame>,
+    max_depth: Option<usize>,
+    // The path to output after the size just output
+    path: Option<Value>,
+}
+
+impl DuIterator {
+    fn output(&mut self, size: u64, path: &Path) -> Value {
+        self.path = Some(Value::String(path.display().to_string()));
+
+        match i64::try_from(size) {
+            Ok(size) => Value::SmallInt(size),
+            Err(_) => Value::BigInt(size.into()),
+        }
+    }
+}
+
+impl Iterator for DuIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(path) = self.path.take() {
+            return Some(path);
+        }
+
+        loop {
+            let frame = match self.stack.last_mut() {
+                Some(frame) => frame,
+                None => {
+                    let root = self.roots.pop_front()?;
+                    let metadata = match std::fs::symlink_metadata(&root) {
+                        Ok(metadata) => metadata,
+                        Err(_) => continue,
+                    };
+
+                    match std::fs::read_dir(&root) {
+                        Ok(entries) if metadata.is_dir() => self.stack.push(Frame {
+                            path: root,
+                            depth: 0,
+                            entries,
+                            total: metadata.len(),
+                        }),
+                        _ => return Some(self.output(metadata.len(), &root)),
+                    }
+                    continue;
+                }
+            };
+
+            match frame.entries.next() {
+                Some(Ok(entry)) => {
+                    let metadata = match entry.metadata() {
+                        Ok(metadata) => metadata,
+                        Err(_) => continue,
+                    };
+
+                    if metadata.is_dir() {
+                        if let Ok(entries) = std::fs::read_dir(entry.path()) {
+                            let depth = frame.depth + 1;
+                            self.stack.push(Frame {
+                                path: entry.path(),
+                                depth,
+                                entries,
+                                total: metadata.len(),
+                            });
+                            continue;
+                        }
+                    }
+                    frame.total += metadata.len();
+                }
+                // Entries that can't be read are skipped
+                Some(Err(_)) => {}
+                None => {
+                    let done = self.stack.pop()?;
+                    if let Some(parent) = self.stack.last_mut() {
+                        parent.total += done.total;
+                    }
+
+                    if self.max_depth.is_none_or(|max| done.depth <= max) {
+                        return Some(self.output(done.total, &done.path));
+                    }
+                }
+            }
+        }
+    }
+}
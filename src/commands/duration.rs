@@ -0,0 +1,51 @@
+use crate::*;
+
+// Reads strings like `2min 3sec` as durations, in nanoseconds. Strings that
+// aren't durations are dropped.
+pub struct IntoDurationCommand;
+
+impl IntoDurationCommand {
+    pub fn signature() -> Signature {
+        Signature::build("into duration")
+            .desc("Reads durations, outputting nanoseconds")
+            .example(
+                "Read a duration",
+                "echo 1.5sec | into duration",
+                vec![Value::SmallInt(1_500_000_000)],
+            )
+    }
+}
+
+impl PipelineElement for IntoDurationCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(args.input.filter_map(|x| match x {
+            Value::SmallInt(_) => Some(x),
+            Value::String(s) => parse_duration(&s).map(Value::SmallInt),
+            _ => None,
+        }))
+    }
+}
+
+// Writes durations in nanoseconds, like timeit's, the way people read them
+pub struct FormatDurationCommand;
+
+impl FormatDurationCommand {
+    pub fn signature() -> Signature {
+        Signature::build("format duration")
+            .desc("Writes nanosecond durations like `2min 3sec`")
+            .example(
+                "Write a duration",
+                "echo 123000000000 | format duration",
+                vec![Value::String("2min 3sec".into())],
+            )
+    }
+}
+
+impl PipelineElement for FormatDurationCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(args.input.filter_map(|x| match x {
+            Value::SmallInt(nanos) => Some(Value::String(format_duration(nanos))),
+            _ => None,
+        }))
+    }
+}
@@ -0,0 +1,23 @@
+use crate::*;
+
+// Outputs its arguments into the pipeline
+pub struct EchoCommand;
+
+impl EchoCommand {
+    pub fn signature() -> Signature {
+        Signature::build("echo")
+            .desc("Outputs its arguments")
+            .rest("values", ArgType::Any, "the values to output")
+            .example(
+                "Output two values",
+                "echo 1 hello",
+                vec![Value::SmallInt(1), Value::String("hello".into())],
+            )
+    }
+}
+
+impl PipelineElement for EchoCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(args.args.into_iter())
+    }
+}
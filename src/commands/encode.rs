@@ -0,0 +1,180 @@
+use crate::*;
+
+use super::hash::to_hex;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// Encodes the UTF-8 bytes of each string as base64
+pub struct EncodeBase64Command;
+
+impl EncodeBase64Command {
+    pub fn signature() -> Signature {
+        Signature::build("encode base64")
+            .desc("Encodes strings as base64")
+            .example(
+                "Encode a string",
+                "echo hello | encode base64",
+                vec![Value::String("aGVsbG8=".into())],
+            )
+    }
+}
+
+impl PipelineElement for EncodeBase64Command {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(args.input.filter_map(|x| match x {
+            Value::String(s) => Some(Value::String(encode_base64(s.as_bytes()))),
+            _ => None,
+        }))
+    }
+}
+
+// Encodes the UTF-8 bytes of each string as lowercase hex
+pub struct EncodeHexCommand;
+
+impl EncodeHexCommand {
+    pub fn signature() -> Signature {
+        Signature::build("encode hex")
+            .desc("Encodes strings as hex")
+            .example(
+                "Encode a string",
+                "echo hi | encode hex",
+                vec![Value::String("6869".into())],
+            )
+    }
+}
+
+impl PipelineElement for EncodeHexCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(args.input.filter_map(|x| match x {
+            Value::String(s) => Some(Value::String(to_hex(s.as_bytes()))),
+            _ => None,
+        }))
+    }
+}
+
+// Decodes base64 strings. Strings that aren't valid base64 are dropped, and
+// decoded bytes that aren't valid UTF-8 are replaced.
+pub struct DecodeBase64Command;
+
+impl DecodeBase64Command {
+    pub fn signature() -> Signature {
+        Signature::build("decode base64")
+            .desc("Decodes base64 strings")
+            .example(
+                "Decode a string",
+                "echo aGVsbG8= | decode base64",
+                vec![Value::String("hello".into())],
+            )
+    }
+}
+
+impl PipelineElement for DecodeBase64Command {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(args.input.filter_map(|x| {
+            match x {
+                Value::String(s) => decode_base64(&s)
+                    .map(|bytes| Value::String(String::from_utf8_lossy(&bytes).to_string())),
+                _ => None,
+            }
+        }))
+    }
+}
+
+// Decodes hex strings. Strings that aren't valid hex are dropped, and decoded
+// bytes that aren't valid UTF-8 are replaced.
+pub struct DecodeHexCommand;
+
+impl DecodeHexCommand {
+    pub fn signature() -> Signature {
+        Signature::build("decode hex")
+            .desc("Decodes hex strings")
+            .example(
+                "Decode a string",
+                "echo 68656c6c6f | decode hex",
+                vec![Value::String("hello".into())],
+            )
+    }
+}
+
+impl PipelineElement for DecodeHexCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(args.input.filter_map(|x| {
+            match x {
+                Value::String(s) => decode_hex(&s)
+                    .map(|bytes| Value::String(String::from_utf8_lossy(&bytes).to_string())),
+                _ => None,
+            }
+        }))
+    }
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut output = String::new();
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as usize) << 16 | (b[1] as usize) << 8 | b[2] as usize;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(BASE64_ALPHABET[(n >> (18 - 6 * i)) & 0x3f] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+
+    output
+}
+
+fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim();
+    let data = s.trim_end_matches('=');
+    let padding = s.len() - data.len();
+
+    // A single character left over can't hold a byte, and padding has to
+    // bring the string up to a whole number of four character groups
+    if data.len() % 4 == 1 || (padding > 0 && (padding > 2 || !s.len().is_multiple_of(4))) {
+        return None;
+    }
+
+    let mut output = vec![];
+    let mut bits = 0u32;
+    let mut bit_count = 0;
+
+    for c in data.bytes() {
+        let value = BASE64_ALPHABET.iter().position(|x| *x == c)? as u32;
+        bits = bits << 6 | value;
+        bit_count += 6;
+
+        if bit_count >= 8 {
+            bit_count -= 8;
+            output.push((bits >> bit_count) as u8);
+            bits &= (1 << bit_count) - 1;
+        }
+    }
+
+    // The bits past the last byte are always zero in a canonical encoding
+    if bits != 0 {
+        return None;
+    }
+
+    Some(output)
+}
+
+pub(crate) fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim();
+    if s.len() % 2 == 1 || !s.bytes().all(|x| x.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::*;
+
+// Runs the block on a timer and outputs what each run outputs. Runs are spaced
+// from when each one was due to start, so a slow run doesn't push the later ones
+// back, and a run that's late starts right away. Falling a whole interval or
+// more behind, say because nothing pulled on the output for a while, restarts
+// the timer rather than running a burst of missed runs to catch up. Without
+// --times the stream never ends on its own; something downstream (like take)
+// has to stop it.
+pub struct EveryCommand {
+    warnings: Warnings,
+    block: Arc<dyn PipelineElement + Send + Sync>,
+}
+
+impl EveryCommand {
+    pub fn new(warnings: Warnings, block: impl PipelineElement + Send + Sync + 'static) -> Self {
+        Self {
+            warnings,
+            block: Arc::new(block),
+        }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("every")
+            .desc("Runs a block on a timer")
+            .required(
+                "interval",
+                ArgType::Int,
+                "how long between runs, in milliseconds",
+            )
+            .named(
+                "times",
+                Some('t'),
+                ArgType::Int,
+                "stop after this many runs",
+            )
+            .rest("args", ArgType::Any, "arguments for the block")
+    }
+}
+
+impl PipelineElement for EveryCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.warnings.arg_error("every", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        // A zero interval would just spin, running the block as fast as it can
+        let interval = match evaluated.get("interval") {
+            Some(Value::SmallInt(ms)) if *ms > 0 => Duration::from_millis(*ms as u64),
+            _ => {
+                self.warnings
+                    .arg_error("every", "`interval` has to be more than zero");
+                return Box::new(std::iter::empty());
+            }
+        };
+        let times = match evaluated.get("times") {
+            Some(Value::SmallInt(n)) if *n >= 0 => Some(*n as usize),
+            Some(_) => {
+                self.warnings
+                    .arg_error("every", "`--times` can't be negative");
+                return Box::new(std::iter::empty());
+            }
+            None => None,
+        };
+
+        Box::new(EveryIterator {
+            block: self.block.clone(),
+            block_args: evaluated.rest().to_vec(),
+            interval,
+            remaining: times,
+            next_run: Instant::now(),
+            current: None,
+        })
+    }
+}
+
+struct EveryIterator {
+    block: Arc<dyn PipelineElement + Send + Sync>,
+    block_args: Vec<Value>,
+    interval: Duration,
+    // How many runs are left, if there's a limit
+    remaining: Option<usize>,
+    next_run: Instant,
+    // The output of the latest run
+    current: Option<ValueIterator>,
+}
+
+impl Iterator for EveryIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(current) = &mut self.current {
+                if let Some(value) = current.next() {
+                    return Some(value);
+                }
+            }
+
+            match &mut self.remaining {
+                Some(0) => return None,
+                Some(remaining) => *remaining -= 1,
+                None => {}
+            }
+
+            let now = Instant::now();
+            if self.next_run > now {
+                std::thread::sleep(self.next_run - now);
+            }
+            self.next_run += self.interval;
+            if self.next_run <= now {
+                self.next_run = now + self.interval;
+            }
+
+            self.current = Some(self.block.start(CommandArgs {
+                input: Box::new(std::iter::empty()),
+                args: self.block_args.clone(),
+                state: State,
+            }));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::*;
+
+// Keeps the values whose text contains the search term
+pub struct FindCommand {
+    warnings: Warnings,
+}
+
+impl FindCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("find")
+            .desc("Keeps only the values containing the given text")
+            .required("term", ArgType::String, "the text to search for")
+            .switch("ignore-case", Some('i'), "match regardless of case")
+            .example(
+                "Find the strings containing \"an\"",
+                "echo banana apple mango | find an",
+                vec![
+                    Value::String("banana".into()),
+                    Value::String("mango".into()),
+                ],
+            )
+    }
+}
+
+impl PipelineElement for FindCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.warnings.arg_error("find", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let ignore_case = evaluated.has("ignore-case");
+        let term = match evaluated.get("term") {
+            Some(Value::String(s)) if ignore_case => s.to_lowercase(),
+            Some(Value::String(s)) => s.clone(),
+            _ => return Box::new(std::iter::empty()),
+        };
+
+        Box::new(args.input.filter(move |x| {
+            let text = format_value(x);
+            if ignore_case {
+                text.to_lowercase().contains(&term)
+            } else {
+                text.contains(&term)
+            }
+        }))
+    }
+}
@@ -0,0 +1,588 @@
+use std::fs::{FileTimes, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::deferred_adapter::DeferredIterator;
+use crate::*;
+
+// The commands that change the filesystem. Paths are resolved against the
+// working directory, and sources can use `*` and `?` wildcards in their last
+// part. Anything that can't be done is reported as a warning, and the rest
+// carries on. With --verbose, each path that was acted on is output.
+//
+// Nothing is changed until the output is pulled on, so building a pipeline with
+// one of these in it, say for an explain, leaves the filesystem alone. Their
+// signatures are marked as having side effects, so previews and dry runs don't
+// run them at all.
+
+// Creates directories, along with any missing parents
+#[derive(Clone)]
+pub struct MkdirCommand {
+    fs: Fs,
+}
+
+impl MkdirCommand {
+    pub fn new(cwd: Arc<WorkingDir>, warnings: Warnings) -> Self {
+        Self {
+            fs: Fs { cwd, warnings },
+        }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("mkdir")
+            .side_effects()
+            .desc("Creates directories")
+            .rest("paths", ArgType::String, "the directories to create")
+            .switch("verbose", Some('v'), "output each directory created")
+    }
+}
+
+impl PipelineElement for MkdirCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let command = self.clone();
+        Box::new(DeferredIterator::new(move || command.run(args.args)))
+    }
+}
+
+impl MkdirCommand {
+    fn run(&self, args: Vec<Value>) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.fs.warnings.arg_error(&Self::signature().name, err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let mut done = vec![];
+        for path in strings(evaluated.rest()) {
+            let path = self.fs.cwd.resolve(path);
+            match std::fs::create_dir_all(&path) {
+                Ok(()) => done.push(path),
+                Err(err) => self.fs.warn("create", &path, err),
+            }
+        }
+
+        output(done, evaluated.has("verbose"))
+    }
+}
+
+// Copies files, or directories with --recursive. Copying several sources, or
+// copying into an existing directory, puts the copies inside it.
+#[derive(Clone)]
+pub struct CpCommand {
+    fs: Fs,
+}
+
+impl CpCommand {
+    pub fn new(cwd: Arc<WorkingDir>, warnings: Warnings) -> Self {
+        Self {
+            fs: Fs { cwd, warnings },
+        }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("cp")
+            .side_effects()
+            .desc("Copies files and directories")
+            .required("source", ArgType::String, "what to copy")
+            .required("destination", ArgType::String, "where to copy it")
+            .switch(
+                "recursive",
+                Some('r'),
+                "copy directories and their contents",
+            )
+            .switch("verbose", Some('v'), "output each file copied")
+    }
+}
+
+impl PipelineElement for CpCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let command = self.clone();
+        Box::new(DeferredIterator::new(move || command.run(args.args)))
+    }
+}
+
+impl CpCommand {
+    fn run(&self, args: Vec<Value>) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.fs.warnings.arg_error(&Self::signature().name, err);
+                return Box::new(std::iter::empty());
+            }
+        };
+        let (sources, destination) = match self.fs.source_and_destination(&evaluated) {
+            Some(x) => x,
+            None => return Box::new(std::iter::empty()),
+        };
+        let recursive = evaluated.has("recursive");
+
+        let mut done = vec![];
+        for (source, target) in targets(&sources, &destination) {
+            if is_dir(&source) {
+                if !recursive {
+                    self.fs.warnings.push(Warning::new(format!(
+                        "not copying `{}`: it's a directory (use --recursive)",
+                        source.display()
+                    )));
+                    continue;
+                }
+                if canonical(&target).starts_with(canonical(&source)) {
+                    self.fs.warnings.push(Warning::new(format!(
+                        "not copying `{}` into itself",
+                        source.display()
+                    )));
+                    continue;
+                }
+            }
+            self.fs.copy(&source, &target, &mut done);
+        }
+
+        output(done, evaluated.has("verbose"))
+    }
+}
+
+// Moves or renames files and directories. Moving several sources, or moving
+// into an existing directory, puts them inside it. Moves to another filesystem
+// are done by copying, then removing the source.
+#[derive(Clone)]
+pub struct MvCommand {
+    fs: Fs,
+}
+
+impl MvCommand {
+    pub fn new(cwd: Arc<WorkingDir>, warnings: Warnings) -> Self {
+        Self {
+            fs: Fs { cwd, warnings },
+        }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("mv")
+            .side_effects()
+            .desc("Moves files and directories")
+            .required("source", ArgType::String, "what to move")
+            .required("destination", ArgType::String, "where to move it")
+            .switch("verbose", Some('v'), "output where each thing moved to")
+    }
+}
+
+impl PipelineElement for MvCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let command = self.clone();
+        Box::new(DeferredIterator::new(move || command.run(args.args)))
+    }
+}
+
+impl MvCommand {
+    fn run(&self, args: Vec<Value>) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.fs.warnings.arg_error(&Self::signature().name, err);
+                return Box::new(std::iter::empty());
+            }
+        };
+        let (sources, destination) = match self.fs.source_and_destination(&evaluated) {
+            Some(x) => x,
+            None => return Box::new(std::iter::empty()),
+        };
+
+        let mut done = vec![];
+        for (source, target) in targets(&sources, &destination) {
+            match std::fs::rename(&source, &target) {
+                Ok(()) => done.push(target),
+                // Renaming only works within a filesystem, so moving across
+                // them is a copy and then a remove. The source is only
+                // removed if all of it was copied.
+                Err(err) if err.kind() == std::io::ErrorKind::CrossesDevices => {
+                    if self.fs.copy(&source, &target, &mut vec![]) {
+                        let removed = if is_dir(&source) {
+                            std::fs::remove_dir_all(&source)
+                        } else {
+                            std::fs::remove_file(&source)
+                        };
+                        match removed {
+                            Ok(()) => done.push(target),
+                            Err(err) => self.fs.warn("remove", &source, err),
+                        }
+                    }
+                }
+                Err(err) => self.fs.warn("move", &source, err),
+            }
+        }
+
+        output(done, evaluated.has("verbose"))
+    }
+}
+
+// Removes files, or directories and everything in them with --recursive
+#[derive(Clone)]
+pub struct RmCommand {
+    fs: Fs,
+}
+
+impl RmCommand {
+    pub fn new(cwd: Arc<WorkingDir>, warnings: Warnings) -> Self {
+        Self {
+            fs: Fs { cwd, warnings },
+        }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("rm")
+            .side_effects()
+            .desc("Removes files and directories")
+            .rest("paths", ArgType::String, "what to remove")
+            .switch(
+                "recursive",
+                Some('r'),
+                "remove directories and their contents",
+            )
+            .switch("verbose", Some('v'), "output each path removed")
+    }
+}
+
+impl PipelineElement for RmCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let command = self.clone();
+        Box::new(DeferredIterator::new(move || command.run(args.args)))
+    }
+}
+
+impl RmCommand {
+    fn run(&self, args: Vec<Value>) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.fs.warnings.arg_error(&Self::signature().name, err);
+                return Box::new(std::iter::empty());
+            }
+        };
+        let recursive = evaluated.has("recursive");
+
+        let mut done = vec![];
+        for pattern in strings(evaluated.rest()) {
+            for path in self.fs.expand(pattern) {
+                let removed = if path.is_dir() && !path.is_symlink() {
+                    if !recursive {
+                        self.fs.warnings.push(Warning::new(format!(
+                            "not removing `{}`: it's a directory (use --recursive)",
+                            path.display()
+                        )));
+                        continue;
+                    }
+                    std::fs::remove_dir_all(&path)
+                } else {
+                    std::fs::remove_file(&path)
+                };
+
+                match removed {
+                    Ok(()) => done.push(path),
+                    Err(err) => self.fs.warn("remove", &path, err),
+                }
+            }
+        }
+
+        output(done, evaluated.has("verbose"))
+    }
+}
+
+// Creates empty files, or sets the access and modification times of files that
+// already exist to now
+#[derive(Clone)]
+pub struct TouchCommand {
+    fs: Fs,
+}
+
+impl TouchCommand {
+    pub fn new(cwd: Arc<WorkingDir>, warnings: Warnings) -> Self {
+        Self {
+            fs: Fs { cwd, warnings },
+        }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("touch")
+            .side_effects()
+            .desc("Creates files or updates their timestamps")
+            .rest("paths", ArgType::String, "the files to touch")
+            .switch(
+                "no-create",
+                Some('c'),
+                "don't create files that are missing",
+            )
+            .switch("verbose", Some('v'), "output each file touched")
+    }
+}
+
+impl PipelineElement for TouchCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let command = self.clone();
+        Box::new(DeferredIterator::new(move || command.run(args.args)))
+    }
+}
+
+impl TouchCommand {
+    fn run(&self, args: Vec<Value>) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.fs.warnings.arg_error(&Self::signature().name, err);
+                return Box::new(std::iter::empty());
+            }
+        };
+        let create = !evaluated.has("no-create");
+
+        let mut done = vec![];
+        for pattern in strings(evaluated.rest()) {
+            for path in self.fs.expand(pattern) {
+                if !create && !path.exists() {
+                    continue;
+                }
+
+                let now = SystemTime::now();
+                let touched = OpenOptions::new()
+                    .create(create)
+                    .append(true)
+                    .open(&path)
+                    .and_then(|file| {
+                        file.set_times(FileTimes::new().set_accessed(now).set_modified(now))
+                    });
+
+                match touched {
+                    Ok(()) => done.push(path),
+                    Err(err) => self.fs.warn("touch", &path, err),
+                }
+            }
+        }
+
+        output(done, evaluated.has("verbose"))
+    }
+}
+
+// What the filesystem commands share: where they are, and where their problems go
+#[derive(Clone)]
+struct Fs {
+    cwd: Arc<WorkingDir>,
+    warnings: Warnings,
+}
+
+impl Fs {
+    fn warn(&self, action: &str, path: &Path, err: std::io::Error) {
+        self.warnings.push(Warning::new(format!(
+            "couldn't {} `{}`: {}",
+            action,
+            path.display(),
+            err
+        )));
+    }
+
+    // Symlinks are copied as links rather than followed, so a link back up the
+    // tree can't send the copy around in circles. Returns whether everything
+    // was copied.
+    fn copy(&self, source: &Path, target: &Path, done: &mut Vec<PathBuf>) -> bool {
+        let metadata = match std::fs::symlink_metadata(source) {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                self.warn("copy", source, err);
+                return false;
+            }
+        };
+
+        let copied = if metadata.file_type().is_symlink() {
+            copy_symlink(source, target)
+        } else if metadata.is_dir() {
+            if let Err(err) = std::fs::create_dir_all(target) {
+                self.warn("create", target, err);
+                return false;
+            }
+            let entries = match std::fs::read_dir(source) {
+                Ok(entries) => entries,
+                Err(err) => {
+                    self.warn("read", source, err);
+                    return false;
+                }
+            };
+
+            let mut complete = true;
+            for entry in entries {
+                complete &= match entry {
+                    Ok(entry) => self.copy(&entry.path(), &target.join(entry.file_name()), done),
+                    Err(err) => {
+                        self.warn("read", source, err);
+                        false
+                    }
+                };
+            }
+            return complete;
+        } else {
+            std::fs::copy(source, target).map(drop)
+        };
+
+        match copied {
+            Ok(()) => {
+                done.push(target.to_path_buf());
+                true
+            }
+            Err(err) => {
+                self.warn("copy", source, err);
+                false
+            }
+        }
+    }
+
+    // The paths matching a pattern. Patterns without wildcards are returned as
+    // they are, whether they exist or not, so the error for a missing path comes
+    // from the operation. Patterns with wildcards that match nothing warn.
+    fn expand(&self, pattern: &str) -> Vec<PathBuf> {
+        let path = self.cwd.resolve(pattern);
+
+        let name = match path.file_name() {
+            Some(name) if is_glob(&name.to_string_lossy()) => name.to_string_lossy().to_string(),
+            _ => return vec![path],
+        };
+        let dir = path.parent().unwrap_or_else(|| Path::new("/"));
+
+        let mut matches: Vec<_> = std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|x| glob_match(&name, &x.file_name().to_string_lossy()))
+            .map(|x| x.path())
+            .collect();
+        matches.sort();
+
+        if matches.is_empty() {
+            self.warnings
+                .push(Warning::new(format!("nothing matches `{}`", pattern)));
+        }
+
+        matches
+    }
+
+    fn source_and_destination(&self, evaluated: &EvaluatedArgs) -> Option<(Vec<PathBuf>, PathBuf)> {
+        match (evaluated.get("source"), evaluated.get("destination")) {
+            (Some(Value::String(source)), Some(Value::String(destination))) => {
+                Some((self.expand(source), self.cwd.resolve(destination)))
+            }
+            _ => None,
+        }
+    }
+}
+
+// Pairs each source with where it should end up. Several sources, or a
+// destination that's an existing directory, means going inside the destination.
+fn targets(sources: &[PathBuf], destination: &Path) -> Vec<(PathBuf, PathBuf)> {
+    let into_dir = sources.len() > 1 || destination.is_dir();
+
+    sources
+        .iter()
+        .map(|source| {
+            let target = match (into_dir, source.file_name()) {
+                (true, Some(name)) => destination.join(name),
+                _ => destination.to_path_buf(),
+            };
+            (source.clone(), target)
+        })
+        .collect()
+}
+
+// Whether the path is a directory itself, rather than a link to one
+fn is_dir(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok_and(|x| x.is_dir())
+}
+
+// The path with links and `..` resolved, even when the end of it doesn't exist
+// yet, like the destination of a copy
+fn canonical(path: &Path) -> PathBuf {
+    for ancestor in path.ancestors() {
+        if let Ok(real) = ancestor.canonicalize() {
+            return match path.strip_prefix(ancestor) {
+                Ok(rest) => real.join(rest),
+                Err(_) => real,
+            };
+        }
+    }
+
+    path.to_path_buf()
+}
+
+#[cfg(unix)]
+fn copy_symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(std::fs::read_link(source)?, target)
+}
+
+// Elsewhere, making links needs privileges we may not have, so the link's
+// target is copied instead
+#[cfg(not(unix))]
+fn copy_symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::fs::copy(source, target).map(drop)
+}
+
+fn strings(values: &[Value]) -> impl Iterator<Item = &str> {
+    values.iter().filter_map(|x| match x {
+        Value::String(s) => Some(s.as_str()),
+        _ => None,
+    })
+}
+
+fn output(done: Vec<PathBuf>, verbose: bool) -> ValueIterator {
+    if !verbose {
+        return Box::new(std::iter::empty());
+    }
+
+    Box::new(
+        done.into_iter()
+            .map(|x| Value::String(x.to_string_lossy().to_string())),
+    )
+}
+
+fn is_glob(name: &str) -> bool {
+    name.contains('*') || name.contains('?')
+}
+
+// Matches a name against a pattern where `*` is any run of characters and `?`
+// is any one character. Hidden files only match patterns starting with `.`.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    if name.starts_with('.') && !pattern.starts_with('.') {
+        return false;
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // The usual backtracking match: on a mismatch, let the last `*` take one
+    // more character and try again from there
+    let (mut p, mut n) = (0, 0);
+    let mut star = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some('?') => {
+                p += 1;
+                n += 1;
+            }
+            Some(c) if *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|x| *x == '*')
+}
@@ -0,0 +1,57 @@
+use crate::*;
+
+use std::sync::Arc;
+
+type GenerateFn = Arc<dyn Fn(Value) -> Option<(Value, Value)> + Send + Sync + 'static>;
+
+// Unfolds a stream from an initial state (the first argument). The step function
+// gets the current state and returns the value to output along with the next
+// state, or None to end the stream. The stream is lazy, so it can be endless
+// as long as something downstream (like take) stops pulling.
+pub struct GenerateCommand {
+    step: GenerateFn,
+}
+
+impl GenerateCommand {
+    pub fn new(step: impl Fn(Value) -> Option<(Value, Value)> + Send + Sync + 'static) -> Self {
+        Self {
+            step: Arc::new(step),
+        }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("generate")
+            .desc("Outputs values unfolded from an initial state")
+            .required("initial", ArgType::Any, "the state to start from")
+    }
+}
+
+impl PipelineElement for GenerateCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        match args.args.into_iter().next() {
+            Some(initial) => Box::new(GenerateIterator {
+                step: self.step.clone(),
+                state: Some(initial),
+            }),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+struct GenerateIterator {
+    step: GenerateFn,
+    state: Option<Value>,
+}
+
+impl Iterator for GenerateIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let state = self.state.take()?;
+
+        let (output, next) = (self.step)(state)?;
+        self.state = Some(next);
+
+        Some(output)
+    }
+}
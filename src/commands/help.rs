@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use crate::registry::is_subcommand;
+use crate::*;
+
+// Lists the available commands, or describes the one named by the arguments
+// along with any subcommands under it. Help works from the signatures registered
+// when it was created, so it should be added after the other commands.
+pub struct HelpCommand {
+    signatures: Vec<Signature>,
+    // Deprecated command names, with their replacements
+    deprecated: HashMap<String, String>,
+}
+
+impl HelpCommand {
+    pub fn new(registry: &CommandRegistry) -> Self {
+        let signatures: Vec<Signature> = registry.signatures().into_iter().cloned().collect();
+        let deprecated = signatures
+            .iter()
+            .filter_map(|x| {
+                let replacement = registry.deprecation(&x.name)?;
+                Some((x.name.clone(), replacement.to_string()))
+            })
+            .collect();
+
+        Self {
+            signatures,
+            deprecated,
+        }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("help")
+            .desc("Lists commands, or shows how to use one")
+            .rest("name", ArgType::String, "the command to describe")
+    }
+}
+
+impl PipelineElement for HelpCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let name: Vec<_> = args
+            .args
+            .iter()
+            .filter_map(|x| match x {
+                Value::String(s) => Some(s.as_str()),
+                _ => None,
+            })
+            .collect();
+        let name = name.join(" ");
+
+        let mut lines = vec![];
+
+        if name.is_empty() {
+            let mut top_level: Vec<_> = self
+                .signatures
+                .iter()
+                .map(|x| x.name.split(' ').next().unwrap_or_default())
+                .collect();
+            top_level.sort_unstable();
+            top_level.dedup();
+
+            for name in top_level {
+                lines.push(self.summary(name));
+            }
+        } else {
+            if let Some(signature) = self.signatures.iter().find(|x| x.name == name) {
+                lines.extend(usage(signature));
+            }
+            if let Some(replacement) = self.deprecated.get(&name) {
+                lines.push(format!("deprecated: use `{}` instead", replacement));
+            }
+
+            let subcommands: Vec<_> = self
+                .signatures
+                .iter()
+                .filter(|x| is_subcommand(&x.name, &name))
+                .collect();
+
+            if !subcommands.is_empty() {
+                lines.push("subcommands:".into());
+                for subcommand in subcommands {
+                    lines.push(format!("  {}", self.summary(&subcommand.name)));
+                }
+            }
+
+            if lines.is_empty() {
+                lines.push(format!("no command named `{}`", name));
+            }
+        }
+
+        Box::new(lines.into_iter().map(Value::String))
+    }
+}
+
+impl HelpCommand {
+    fn summary(&self, name: &str) -> String {
+        let summary = match self.signatures.iter().find(|x| x.name == name) {
+            Some(signature) if !signature.description.is_empty() => {
+                format!("{} - {}", name, signature.description)
+            }
+            _ => name.to_string(),
+        };
+
+        match self.deprecated.get(name) {
+            Some(replacement) => format!("{} (deprecated, use `{}`)", summary, replacement),
+            None => summary,
+        }
+    }
+}
+
+fn usage(signature: &Signature) -> Vec<String> {
+    let mut call = signature.name.clone();
+    for param in &signature.positional {
+        if param.optional {
+            call.push_str(&format!(" [{}]", param.name));
+        } else {
+            call.push_str(&format!(" <{}>", param.name));
+        }
+    }
+    if let Some(rest) = &signature.rest {
+        call.push_str(&format!(" ...{}", rest.name));
+    }
+
+    let mut lines = vec![signature.description.clone(), format!("usage: {}", call)];
+
+    for param in signature.positional.iter().chain(signature.rest.iter()) {
+        lines.push(format!(
+            "  {} ({:?}) - {}",
+            param.name, param.arg_type, param.description
+        ));
+    }
+
+    for flag in &signature.flags {
+        let short = match flag.short {
+            Some(short) => format!(", -{}", short),
+            None => String::new(),
+        };
+        lines.push(format!("  --{}{} - {}", flag.name, short, flag.description));
+    }
+
+    lines
+}
@@ -0,0 +1,72 @@
+use crate::*;
+
+use std::sync::Arc;
+
+// Reads a line from the host, showing the first argument (if any) as the prompt
+pub struct InputCommand {
+    io: Arc<dyn HostIo>,
+}
+
+impl InputCommand {
+    pub fn new(io: Arc<dyn HostIo>) -> Self {
+        Self { io }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("input")
+            .desc("Reads a line of input from the user")
+            .side_effects()
+            .optional("prompt", ArgType::String, "the prompt to show")
+            .switch(
+                "suppress-output",
+                Some('s'),
+                "don't show the input as it's typed, for secrets",
+            )
+    }
+}
+
+impl PipelineElement for InputCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.io.write_err(&format!("input: {}\n", err));
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let prompt = match evaluated.get("prompt") {
+            Some(Value::String(s)) => s.clone(),
+            _ => String::new(),
+        };
+
+        Box::new(InputIterator {
+            io: self.io.clone(),
+            prompt,
+            suppress_output: evaluated.has("suppress-output"),
+            done: false,
+        })
+    }
+}
+
+struct InputIterator {
+    io: Arc<dyn HostIo>,
+    prompt: String,
+    suppress_output: bool,
+    done: bool,
+}
+
+impl Iterator for InputIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        self.done = true;
+        self.io
+            .read_line(&self.prompt, self.suppress_output)
+            .map(Value::String)
+    }
+}
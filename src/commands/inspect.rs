@@ -1,9 +1,28 @@
 use crate::*;
 
-pub struct InspectCommand;
+use std::sync::Arc;
+
+pub struct InspectCommand {
+    io: Arc<dyn HostIo>,
+}
+
+impl InspectCommand {
+    pub fn new(io: Arc<dyn HostIo>) -> Self {
+        Self { io }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("inspect")
+            .desc("Writes out each value as it passes through")
+            .side_effects()
+    }
+}
 
 impl PipelineElement for InspectCommand {
     fn start(&self, args: CommandArgs) -> ValueIterator {
-        Box::new(args.input.inspect(|x| println!("{:?}", x)))
+        let io = self.io.clone();
+        Box::new(args.input.inspect(move |x| {
+            io.write_out(&format!("{:?}\n", x));
+        }))
     }
 }
@@ -0,0 +1,37 @@
+use crate::merge_adapter::MergeIterator;
+use crate::*;
+
+// Runs each block at the same time, along with the input stream, and outputs
+// their values as they arrive. The order between blocks isn't fixed, though
+// each block's own values stay in order.
+pub struct InterleaveCommand {
+    blocks: Vec<Box<dyn PipelineElement>>,
+}
+
+impl InterleaveCommand {
+    pub fn new(blocks: Vec<Box<dyn PipelineElement>>) -> Self {
+        Self { blocks }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("interleave")
+            .desc("Merges the output of several blocks as it arrives")
+            .rest("args", ArgType::Any, "arguments for each block")
+    }
+}
+
+impl PipelineElement for InterleaveCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let mut sources = vec![args.input];
+
+        for block in &self.blocks {
+            sources.push(block.start(CommandArgs {
+                input: Box::new(std::iter::empty()),
+                args: args.args.clone(),
+                state: State,
+            }));
+        }
+
+        Box::new(MergeIterator::new(sources))
+    }
+}
@@ -3,6 +3,18 @@ use num_bigint::BigInt;
 
 pub struct LengthCommand;
 
+impl LengthCommand {
+    pub fn signature() -> Signature {
+        Signature::build("length")
+            .desc("Outputs how many values are in the stream")
+            .example(
+                "Count the values in a stream",
+                "count | take 10 | length",
+                vec![Value::BigInt(10.into())],
+            )
+    }
+}
+
 impl PipelineElement for LengthCommand {
     fn start(&self, args: CommandArgs) -> ValueIterator {
         Box::new(LengthIterator {
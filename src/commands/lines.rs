@@ -0,0 +1,67 @@
+use crate::*;
+use std::collections::VecDeque;
+
+// Splits incoming text into lines. Text can arrive in chunks (like from `open`)
+// that break lines in the middle, so partial lines are held on to until the rest
+// of the line arrives.
+pub struct LinesCommand;
+
+impl LinesCommand {
+    pub fn signature() -> Signature {
+        Signature::build("lines")
+            .desc("Splits text into lines")
+            .example(
+                "Split text into lines",
+                "echo \"a\nb\" | lines",
+                vec![Value::String("a".into()), Value::String("b".into())],
+            )
+    }
+}
+
+impl PipelineElement for LinesCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(LinesIterator {
+            input: args.input,
+            partial: String::new(),
+            lines: VecDeque::new(),
+        })
+    }
+}
+
+struct LinesIterator {
+    input: ValueIterator,
+    partial: String,
+    lines: VecDeque<Value>,
+}
+
+impl Iterator for LinesIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.lines.is_empty() {
+            match self.input.next() {
+                Some(Value::String(s)) => {
+                    self.partial.push_str(&s);
+
+                    while let Some(idx) = self.partial.find('\n') {
+                        let mut line: String = self.partial.drain(..=idx).collect();
+                        line.pop();
+                        if line.ends_with('\r') {
+                            line.pop();
+                        }
+                        self.lines.push_back(Value::String(line));
+                    }
+                }
+                // Anything that isn't text doesn't have lines, so pass it through
+                Some(other) => self.lines.push_back(other),
+                None if !self.partial.is_empty() => {
+                    let line = std::mem::take(&mut self.partial);
+                    self.lines.push_back(Value::String(line));
+                }
+                None => return None,
+            }
+        }
+
+        self.lines.pop_front()
+    }
+}
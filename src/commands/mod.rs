@@ -1,25 +1,103 @@
 mod append;
+mod bits;
+mod breakpoint;
+mod cd;
+mod char_;
+mod clip;
 mod contains;
 mod count;
+mod drop;
+mod du;
+mod duration;
+mod echo;
+mod encode;
+mod every;
+mod find;
+mod fs;
+mod generate;
+mod hash;
+mod help;
+mod input;
 mod inspect;
+mod interleave;
 mod length;
+mod lines;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod notify;
+mod nuon;
 mod open;
 mod par_each;
+mod path;
 mod prepend;
+mod print;
+mod random;
+mod seq_date;
+mod sort;
+mod split_row;
+mod stdin;
 mod str_length;
 mod sum;
 mod take;
+mod tee;
+mod timeit;
+mod uniq;
+mod watch;
 mod where_;
+mod which;
 
 pub use append::AppendCommand;
+pub use bits::{
+    BitsAndCommand, BitsNotCommand, BitsOrCommand, BitsRolCommand, BitsRorCommand, BitsShlCommand,
+    BitsShrCommand, BitsXorCommand,
+};
+pub use breakpoint::BreakpointCommand;
+pub use cd::{CdCommand, PwdCommand};
+pub use char_::CharCommand;
+pub use clip::{ClipCopyCommand, ClipPasteCommand};
 pub use contains::ContainsCommand;
 pub use count::CountCommand;
+pub use drop::DropCommand;
+pub use du::DuCommand;
+pub use duration::{FormatDurationCommand, IntoDurationCommand};
+pub use echo::EchoCommand;
+pub use encode::{DecodeBase64Command, DecodeHexCommand, EncodeBase64Command, EncodeHexCommand};
+pub use every::EveryCommand;
+pub use find::FindCommand;
+pub use fs::{CpCommand, MkdirCommand, MvCommand, RmCommand, TouchCommand};
+pub use generate::GenerateCommand;
+pub use hash::{HashMd5Command, HashSha256Command};
+pub use help::HelpCommand;
+pub use input::InputCommand;
 pub use inspect::InspectCommand;
+pub use interleave::InterleaveCommand;
 pub use length::LengthCommand;
+pub use lines::LinesCommand;
+#[cfg(feature = "msgpack")]
+pub use msgpack::{FromMsgpackCommand, ToMsgpackCommand};
+pub use notify::NotifyCommand;
+pub use nuon::{FromNuonCommand, ToNuonCommand};
 pub use open::OpenCommand;
 pub use par_each::ParEachCommand;
+pub use path::{
+    PathBasenameCommand, PathDirnameCommand, PathExistsCommand, PathExpandCommand, PathJoinCommand,
+    PathTypeCommand,
+};
 pub use prepend::PrependCommand;
+pub use print::PrintCommand;
+pub use random::{
+    RandomBoolCommand, RandomCharsCommand, RandomDiceCommand, RandomIntCommand, RandomUuidCommand,
+};
+pub use seq_date::SeqDateCommand;
+pub use sort::SortCommand;
+pub use split_row::SplitRowCommand;
+pub use stdin::StdinCommand;
 pub use str_length::StrLengthCommand;
 pub use sum::SumCommand;
 pub use take::TakeCommand;
+pub use tee::TeeCommand;
+pub use timeit::TimeitCommand;
+pub use uniq::UniqCommand;
+pub use watch::WatchCommand;
 pub use where_::WhereCommand;
+pub use which::WhichCommand;
@@ -0,0 +1,73 @@
+use crate::*;
+
+use super::encode::decode_hex;
+use super::hash::to_hex;
+
+// There's no binary value, so MessagePack bytes travel through the pipeline as
+// hex strings, like `encode hex` outputs
+
+// Replaces each value with its MessagePack encoding, as hex
+pub struct ToMsgpackCommand;
+
+impl ToMsgpackCommand {
+    pub fn signature() -> Signature {
+        Signature::build("to msgpack")
+            .desc("Converts values to MessagePack, as hex")
+            .example(
+                "Convert a string",
+                "echo hi | to msgpack",
+                vec![Value::String("a26869".into())],
+            )
+    }
+}
+
+impl PipelineElement for ToMsgpackCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(
+            args.input
+                .filter_map(|x| to_msgpack(&x).map(|bytes| Value::String(to_hex(&bytes)))),
+        )
+    }
+}
+
+// Reads the values in each hex string of MessagePack. A string can hold several
+// values one after another, and each is output in turn. Strings that aren't
+// valid hex, or don't hold valid MessagePack, are dropped.
+pub struct FromMsgpackCommand;
+
+impl FromMsgpackCommand {
+    pub fn signature() -> Signature {
+        Signature::build("from msgpack")
+            .desc("Reads values from MessagePack, given as hex")
+            .example(
+                "Read a number back",
+                "echo 300 | to msgpack | from msgpack",
+                vec![Value::SmallInt(300)],
+            )
+    }
+}
+
+impl PipelineElement for FromMsgpackCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(args.input.flat_map(|x| {
+            match x {
+                Value::String(s) => decode_hex(&s)
+                    .and_then(|x| read_all(&x))
+                    .unwrap_or_default(),
+                _ => vec![],
+            }
+        }))
+    }
+}
+
+fn read_all(mut bytes: &[u8]) -> Option<Vec<Value>> {
+    let mut values = vec![];
+
+    while !bytes.is_empty() {
+        let (value, used) = from_msgpack(bytes).ok()?;
+        values.push(value);
+        bytes = &bytes[used..];
+    }
+
+    Some(values)
+}
@@ -0,0 +1,70 @@
+use crate::*;
+
+use std::sync::Arc;
+
+// Passes its input through unchanged, then sends a notification through the
+// host once the input runs out. Put at the end of a long pipeline, this says
+// when it's done.
+pub struct NotifyCommand {
+    io: Arc<dyn HostIo>,
+}
+
+impl NotifyCommand {
+    pub fn new(io: Arc<dyn HostIo>) -> Self {
+        Self { io }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("notify")
+            .side_effects()
+            .desc("Sends a notification once the input is done")
+            .required("title", ArgType::String, "what the notification says")
+            .optional("body", ArgType::String, "more detail")
+    }
+}
+
+impl PipelineElement for NotifyCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.io.write_err(&format!("notify: {}\n", err));
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let text = |name| match evaluated.get(name) {
+            Some(Value::String(s)) => s.clone(),
+            _ => String::new(),
+        };
+
+        Box::new(NotifyIterator {
+            input: args.input,
+            io: self.io.clone(),
+            notification: Some((text("title"), text("body"))),
+        })
+    }
+}
+
+struct NotifyIterator {
+    input: ValueIterator,
+    io: Arc<dyn HostIo>,
+    // The title and body, until they've been sent
+    notification: Option<(String, String)>,
+}
+
+impl Iterator for NotifyIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.input.next();
+
+        if next.is_none() {
+            if let Some((title, body)) = self.notification.take() {
+                self.io.notify(&title, &body);
+            }
+        }
+
+        next
+    }
+}
@@ -0,0 +1,46 @@
+use crate::*;
+
+// Replaces each value with its NUON text
+pub struct ToNuonCommand;
+
+impl ToNuonCommand {
+    pub fn signature() -> Signature {
+        Signature::build("to nuon")
+            .desc("Converts values to NUON text")
+            .example(
+                "Convert a string",
+                "echo hello | to nuon",
+                vec![Value::String("\"hello\"".into())],
+            )
+    }
+}
+
+impl PipelineElement for ToNuonCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(args.input.filter_map(|x| to_nuon(&x).map(Value::String)))
+    }
+}
+
+// Reads each string as NUON text. Strings that aren't valid NUON are dropped.
+pub struct FromNuonCommand;
+
+impl FromNuonCommand {
+    pub fn signature() -> Signature {
+        Signature::build("from nuon")
+            .desc("Reads values from NUON text")
+            .example(
+                "Read a number back",
+                "echo 12 | to nuon | from nuon",
+                vec![Value::SmallInt(12)],
+            )
+    }
+}
+
+impl PipelineElement for FromNuonCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(args.input.filter_map(|x| match x {
+            Value::String(s) => from_nuon(&s).ok(),
+            _ => None,
+        }))
+    }
+}
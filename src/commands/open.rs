@@ -2,8 +2,23 @@ use crate::*;
 use std::{fs::File, io::BufRead};
 
 use std::io::BufReader;
+use std::sync::Arc;
 
-pub struct OpenCommand;
+pub struct OpenCommand {
+    cwd: Arc<WorkingDir>,
+}
+
+impl OpenCommand {
+    pub fn new(cwd: Arc<WorkingDir>) -> Self {
+        Self { cwd }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("open")
+            .desc("Reads a file as a stream of strings")
+            .required("path", ArgType::String, "the file to open")
+    }
+}
 
 impl PipelineElement for OpenCommand {
     fn start(&self, args: CommandArgs) -> ValueIterator {
@@ -12,7 +27,7 @@ impl PipelineElement for OpenCommand {
             _ => return Box::new(std::iter::empty()),
         };
 
-        let f = File::open(&fname).unwrap();
+        let f = File::open(self.cwd.resolve(&fname)).unwrap();
         let buf_reader = BufReader::with_capacity(1024 * 32, f);
 
         Box::new(OpenIterator { buf_reader })
@@ -4,6 +4,19 @@ use crate::par_iter_adapter::PartitionedParallelIterator;
 
 pub struct ParEachCommand;
 
+impl ParEachCommand {
+    pub fn signature() -> Signature {
+        Signature::build("par-each")
+            .desc("Processes values in parallel batches")
+            .required(
+                "per-worker",
+                ArgType::Int,
+                "how many values each worker gets",
+            )
+            .required("workers", ArgType::Int, "how many workers to use")
+    }
+}
+
 impl PipelineElement for ParEachCommand {
     fn start(&self, args: CommandArgs) -> ValueIterator {
         match (args.args.get(0), args.args.get(1)) {
@@ -0,0 +1,209 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::*;
+
+// The path commands work on paths given as strings, one per input value, and
+// drop any input that isn't a string
+
+fn map_paths(
+    input: ValueIterator,
+    f: impl Fn(&Path) -> Option<String> + Send + Sync + 'static,
+) -> ValueIterator {
+    Box::new(input.filter_map(move |x| match x {
+        Value::String(s) => f(Path::new(&s)).map(Value::String),
+        _ => None,
+    }))
+}
+
+// Adds the arguments onto the end of each path
+pub struct PathJoinCommand {
+    warnings: Warnings,
+}
+
+impl PathJoinCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("path join")
+            .desc("Joins parts onto paths")
+            .rest("parts", ArgType::String, "the parts to add")
+            .example(
+                "Join parts onto a path",
+                "echo a | path join b c.txt",
+                vec![Value::String(
+                    Path::new("a")
+                        .join("b")
+                        .join("c.txt")
+                        .to_string_lossy()
+                        .into(),
+                )],
+            )
+    }
+}
+
+impl PipelineElement for PathJoinCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let parts: Vec<String> = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated
+                .rest()
+                .iter()
+                .filter_map(|x| match x {
+                    Value::String(s) => Some(s.clone()),
+                    _ => None,
+                })
+                .collect(),
+            Err(err) => {
+                self.warnings.arg_error("path join", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        map_paths(args.input, move |path| {
+            let joined = parts.iter().fold(path.to_path_buf(), |acc, x| acc.join(x));
+            Some(joined.to_string_lossy().into())
+        })
+    }
+}
+
+// Replaces each path with the directory it's in. Paths with no parent, like
+// `/`, are dropped.
+pub struct PathDirnameCommand;
+
+impl PathDirnameCommand {
+    pub fn signature() -> Signature {
+        Signature::build("path dirname")
+            .desc("Outputs the directory part of paths")
+            .example(
+                "Get the directory of a file",
+                "echo a/b/c.txt | path dirname",
+                vec![Value::String("a/b".into())],
+            )
+    }
+}
+
+impl PipelineElement for PathDirnameCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        map_paths(args.input, |path| {
+            path.parent().map(|x| x.to_string_lossy().into())
+        })
+    }
+}
+
+// Replaces each path with its last part
+pub struct PathBasenameCommand;
+
+impl PathBasenameCommand {
+    pub fn signature() -> Signature {
+        Signature::build("path basename")
+            .desc("Outputs the last part of paths")
+            .example(
+                "Get the name of a file",
+                "echo a/b/c.txt | path basename",
+                vec![Value::String("c.txt".into())],
+            )
+    }
+}
+
+impl PipelineElement for PathBasenameCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        map_paths(args.input, |path| {
+            path.file_name().map(|x| x.to_string_lossy().into())
+        })
+    }
+}
+
+// Replaces each path with the full path it means from the working directory,
+// with `~` expanded. Paths that exist also have `..` and links resolved.
+pub struct PathExpandCommand {
+    cwd: Arc<WorkingDir>,
+}
+
+impl PathExpandCommand {
+    pub fn new(cwd: Arc<WorkingDir>) -> Self {
+        Self { cwd }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("path expand").desc("Expands paths to full paths")
+    }
+}
+
+impl PipelineElement for PathExpandCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let cwd = self.cwd.clone();
+
+        map_paths(args.input, move |path| {
+            let resolved = cwd.resolve(path);
+            let expanded = resolved.canonicalize().unwrap_or(resolved);
+            Some(expanded.to_string_lossy().into())
+        })
+    }
+}
+
+// Keeps only the paths that exist. There's no boolean value, so this filters
+// rather than outputting whether each one does.
+pub struct PathExistsCommand {
+    cwd: Arc<WorkingDir>,
+}
+
+impl PathExistsCommand {
+    pub fn new(cwd: Arc<WorkingDir>) -> Self {
+        Self { cwd }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("path exists").desc("Keeps only paths that exist")
+    }
+}
+
+impl PipelineElement for PathExistsCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let cwd = self.cwd.clone();
+
+        Box::new(args.input.filter(move |x| match x {
+            Value::String(s) => cwd.resolve(s).exists(),
+            _ => false,
+        }))
+    }
+}
+
+// Replaces each path with what it is: `file`, `dir` or `symlink`. Paths that
+// don't exist are dropped.
+pub struct PathTypeCommand {
+    cwd: Arc<WorkingDir>,
+}
+
+impl PathTypeCommand {
+    pub fn new(cwd: Arc<WorkingDir>) -> Self {
+        Self { cwd }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("path type").desc("Outputs what kind of thing paths are")
+    }
+}
+
+impl PipelineElement for PathTypeCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let cwd = self.cwd.clone();
+
+        map_paths(args.input, move |path| {
+            let file_type = std::fs::symlink_metadata(cwd.resolve(path))
+                .ok()?
+                .file_type();
+
+            let kind = if file_type.is_symlink() {
+                "symlink"
+            } else if file_type.is_dir() {
+                "dir"
+            } else {
+                "file"
+            };
+
+            Some(kind.into())
+        })
+    }
+}
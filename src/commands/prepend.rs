@@ -2,6 +2,19 @@ use crate::*;
 
 pub struct PrependCommand;
 
+impl PrependCommand {
+    pub fn signature() -> Signature {
+        Signature::build("prepend")
+            .desc("Adds a value to the start of the stream")
+            .required("value", ArgType::Any, "the value to add")
+            .example(
+                "Add a value to the start",
+                "echo 1 2 | prepend 0",
+                vec![Value::SmallInt(0), Value::SmallInt(1), Value::SmallInt(2)],
+            )
+    }
+}
+
 impl PipelineElement for PrependCommand {
     fn start(&self, args: CommandArgs) -> ValueIterator {
         let step = std::iter::once(args.args[0].clone());
@@ -0,0 +1,66 @@
+use crate::*;
+
+use std::sync::Arc;
+
+// Writes its arguments, then each value coming in, to the host's output. Nothing
+// is passed on down the pipeline.
+pub struct PrintCommand {
+    io: Arc<dyn HostIo>,
+}
+
+impl PrintCommand {
+    pub fn new(io: Arc<dyn HostIo>) -> Self {
+        Self { io }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("print")
+            .desc("Writes its arguments, then its input, to the output")
+            .side_effects()
+            .rest("values", ArgType::Any, "the values to write")
+            .switch("stderr", Some('e'), "write to stderr instead")
+    }
+}
+
+impl PipelineElement for PrintCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.io.write_err(&format!("print: {}\n", err));
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let values = evaluated.rest().to_vec().into_iter().chain(args.input);
+
+        Box::new(PrintIterator {
+            io: self.io.clone(),
+            to_stderr: evaluated.has("stderr"),
+            values: Box::new(values),
+        })
+    }
+}
+
+struct PrintIterator {
+    io: Arc<dyn HostIo>,
+    to_stderr: bool,
+    values: ValueIterator,
+}
+
+impl Iterator for PrintIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for value in &mut self.values {
+            let text = format!("{}\n", format_value(&value));
+            if self.to_stderr {
+                self.io.write_err(&text);
+            } else {
+                self.io.write_out(&text);
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,232 @@
+use crate::*;
+
+use std::sync::Arc;
+
+const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+pub struct RandomIntCommand {
+    random: Arc<RandomSource>,
+    warnings: Warnings,
+}
+
+impl RandomIntCommand {
+    pub fn new(random: Arc<RandomSource>, warnings: Warnings) -> Self {
+        Self { random, warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("random int")
+            .desc("Outputs a random integer between min and max, inclusive")
+            .optional_with_default(
+                "min",
+                ArgType::Int,
+                Value::SmallInt(0),
+                "the smallest value",
+            )
+            .optional_with_default(
+                "max",
+                ArgType::Int,
+                Value::SmallInt(i64::MAX),
+                "the largest value",
+            )
+    }
+}
+
+impl PipelineElement for RandomIntCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.warnings.arg_error("random int", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        match (evaluated.get("min"), evaluated.get("max")) {
+            (Some(Value::SmallInt(min)), Some(Value::SmallInt(max))) if min <= max => {
+                let value = self.random.range(*min, *max);
+                Box::new(std::iter::once(Value::SmallInt(value)))
+            }
+            _ => {
+                self.warnings
+                    .arg_error("random int", "`min` can't be more than `max`");
+                Box::new(std::iter::empty())
+            }
+        }
+    }
+}
+
+// Outputs a random (version 4) UUID
+pub struct RandomUuidCommand {
+    random: Arc<RandomSource>,
+}
+
+impl RandomUuidCommand {
+    pub fn new(random: Arc<RandomSource>) -> Self {
+        Self { random }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("random uuid").desc("Outputs a random UUID")
+    }
+}
+
+impl PipelineElement for RandomUuidCommand {
+    fn start(&self, _: CommandArgs) -> ValueIterator {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.random.next_u64().to_be_bytes());
+        bytes[8..].copy_from_slice(&self.random.next_u64().to_be_bytes());
+
+        // Mark it as version 4, variant 1
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        let hex: String = bytes.iter().map(|x| format!("{:02x}", x)).collect();
+        let uuid = format!(
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        );
+
+        Box::new(std::iter::once(Value::String(uuid)))
+    }
+}
+
+// Outputs a string of random letters and digits
+pub struct RandomCharsCommand {
+    random: Arc<RandomSource>,
+    warnings: Warnings,
+}
+
+impl RandomCharsCommand {
+    pub fn new(random: Arc<RandomSource>, warnings: Warnings) -> Self {
+        Self { random, warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("random chars")
+            .desc("Outputs a string of random letters and digits")
+            .named(
+                "length",
+                Some('l'),
+                ArgType::Int,
+                "how many characters (25 by default)",
+            )
+    }
+}
+
+impl PipelineElement for RandomCharsCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let length = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => match evaluated.get("length") {
+                Some(Value::SmallInt(n)) if *n >= 0 => *n as usize,
+                Some(_) => {
+                    self.warnings
+                        .arg_error("random chars", "`--length` can't be negative");
+                    return Box::new(std::iter::empty());
+                }
+                None => 25,
+            },
+            Err(err) => {
+                self.warnings.arg_error("random chars", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let chars: String = (0..length)
+            .map(|_| CHARS[self.random.range(0, CHARS.len() as i64 - 1) as usize] as char)
+            .collect();
+
+        Box::new(std::iter::once(Value::String(chars)))
+    }
+}
+
+// Outputs 1 or 0 with even odds. There's no boolean value, so these stand in
+// for true and false.
+pub struct RandomBoolCommand {
+    random: Arc<RandomSource>,
+}
+
+impl RandomBoolCommand {
+    pub fn new(random: Arc<RandomSource>) -> Self {
+        Self { random }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("random bool").desc("Outputs 1 or 0 at random")
+    }
+}
+
+impl PipelineElement for RandomBoolCommand {
+    fn start(&self, _: CommandArgs) -> ValueIterator {
+        let value = (self.random.next_u64() >> 63) as i64;
+
+        Box::new(std::iter::once(Value::SmallInt(value)))
+    }
+}
+
+// Outputs the results of rolling dice, one value per die
+pub struct RandomDiceCommand {
+    random: Arc<RandomSource>,
+    warnings: Warnings,
+}
+
+impl RandomDiceCommand {
+    pub fn new(random: Arc<RandomSource>, warnings: Warnings) -> Self {
+        Self { random, warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("random dice")
+            .desc("Rolls dice, outputting each result")
+            .named(
+                "dice",
+                Some('d'),
+                ArgType::Int,
+                "how many dice (1 by default)",
+            )
+            .named(
+                "sides",
+                Some('s'),
+                ArgType::Int,
+                "how many sides each die has (6 by default)",
+            )
+    }
+}
+
+impl PipelineElement for RandomDiceCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.warnings.arg_error("random dice", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let dice = match evaluated.get("dice") {
+            Some(Value::SmallInt(n)) if *n >= 0 => *n,
+            Some(_) => {
+                self.warnings
+                    .arg_error("random dice", "`--dice` can't be negative");
+                return Box::new(std::iter::empty());
+            }
+            None => 1,
+        };
+        let sides = match evaluated.get("sides") {
+            Some(Value::SmallInt(n)) if *n >= 1 => *n,
+            Some(_) => {
+                self.warnings
+                    .arg_error("random dice", "`--sides` has to be at least 1");
+                return Box::new(std::iter::empty());
+            }
+            None => 6,
+        };
+
+        let random = self.random.clone();
+        Box::new((0..dice).map(move |_| Value::SmallInt(random.range(1, sides))))
+    }
+}
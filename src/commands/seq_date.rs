@@ -0,0 +1,174 @@
+use crate::*;
+
+// Outputs a sequence of dates as YYYY-MM-DD strings, starting from the given
+// date and stepping by a number of days. Without --end-date or --days the
+// sequence runs until it leaves the years 0000 to 9999, so something downstream
+// (like take) usually needs to stop it.
+pub struct SeqDateCommand {
+    warnings: Warnings,
+}
+
+impl SeqDateCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("seq date")
+            .desc("Outputs a sequence of dates")
+            .required("begin", ArgType::String, "the first date, as YYYY-MM-DD")
+            .named(
+                "end-date",
+                Some('e'),
+                ArgType::String,
+                "the last date, as YYYY-MM-DD",
+            )
+            .named("days", Some('n'), ArgType::Int, "how many dates to output")
+            .named(
+                "increment",
+                Some('i'),
+                ArgType::Int,
+                "how many days between dates (1 by default)",
+            )
+            .example(
+                "Three days across the end of February",
+                "seq date 2020-02-28 -n 3",
+                vec![
+                    Value::String("2020-02-28".into()),
+                    Value::String("2020-02-29".into()),
+                    Value::String("2020-03-01".into()),
+                ],
+            )
+    }
+}
+
+impl PipelineElement for SeqDateCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.warnings.arg_error("seq date", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let (begin, end, days, increment) = match arguments(&evaluated) {
+            Ok(x) => x,
+            Err(err) => {
+                self.warnings.arg_error("seq date", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        // The sequence ends if it steps outside the years we can write
+        let dates = (0..)
+            .map_while(move |i: i64| {
+                let day = i.checked_mul(increment)?.checked_add(begin)?;
+                (FIRST_DAY..=LAST_DAY).contains(&day).then_some(day)
+            })
+            .take_while(move |day| match end {
+                Some(end) if increment > 0 => *day <= end,
+                Some(end) => *day >= end,
+                None => true,
+            })
+            .map(|day| Value::String(format_date(day)));
+
+        match days {
+            Some(days) => Box::new(dates.take(days)),
+            None => Box::new(dates),
+        }
+    }
+}
+
+// The first day, the last day (if any), how many days to output (if limited),
+// and the step between them
+fn arguments(evaluated: &EvaluatedArgs) -> Result<(i64, Option<i64>, Option<usize>, i64), String> {
+    let begin = match evaluated.get("begin") {
+        Some(Value::String(s)) => parse_date(s).ok_or_else(|| not_a_date(s))?,
+        _ => return Err(ArgError::MissingPositional("begin".into()).to_string()),
+    };
+    let end = match evaluated.get("end-date") {
+        Some(Value::String(s)) => Some(parse_date(s).ok_or_else(|| not_a_date(s))?),
+        _ => None,
+    };
+    let days = match evaluated.get("days") {
+        Some(Value::SmallInt(n)) if *n >= 0 => Some(*n as usize),
+        Some(_) => return Err("`--days` can't be negative".into()),
+        None => None,
+    };
+    let increment = match evaluated.get("increment") {
+        Some(Value::SmallInt(n)) if *n != 0 => *n,
+        Some(_) => return Err("`--increment` can't be zero".into()),
+        None => 1,
+    };
+
+    Ok((begin, end, days, increment))
+}
+
+// Dates are handled as a count of days since 1970-01-01, using the proleptic
+// Gregorian calendar. Only four-digit years are supported.
+
+const FIRST_DAY: i64 = days_from_civil(0, 1, 1);
+const LAST_DAY: i64 = days_from_civil(9999, 12, 31);
+
+fn parse_date(s: &str) -> Option<i64> {
+    let mut parts = s.trim().splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+
+    if !(0..=9999).contains(&year)
+        || !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+    {
+        return None;
+    }
+
+    Some(days_from_civil(year, month, day))
+}
+
+fn not_a_date(s: &str) -> String {
+    format!("`{}` isn't a date between 0000-01-01 and 9999-12-31", s)
+}
+
+fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// These count in 400-year eras starting in March, so that the leap day falls
+// at the end of the year
+const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
@@ -0,0 +1,51 @@
+use crate::deferred_adapter::DeferredIterator;
+use crate::*;
+
+// Sorts the stream. Every value has to be read before the first is output.
+pub struct SortCommand {
+    warnings: Warnings,
+}
+
+impl SortCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("sort")
+            .desc("Sorts the values of the stream")
+            .switch("reverse", Some('r'), "sort from largest to smallest")
+            .example(
+                "Sort numbers",
+                "echo 3 1 2 | sort",
+                vec![Value::SmallInt(1), Value::SmallInt(2), Value::SmallInt(3)],
+            )
+    }
+}
+
+impl PipelineElement for SortCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let reverse = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated.has("reverse"),
+            Err(err) => {
+                self.warnings.arg_error("sort", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        // Nothing is read until the first value is pulled, so building a
+        // pipeline with an endless source ahead of sort doesn't hang
+        let input = args.input;
+        Box::new(DeferredIterator::new(move || {
+            let mut values: Vec<_> = input.collect();
+            // A stable sort, so equal values keep their order either way
+            if reverse {
+                values.sort_by(|a, b| compare_values(b, a));
+            } else {
+                values.sort_by(compare_values);
+            }
+
+            Box::new(values.into_iter())
+        }))
+    }
+}
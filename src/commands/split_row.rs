@@ -0,0 +1,52 @@
+use crate::*;
+
+// Splits each string into several, one per row, at the given separator
+pub struct SplitRowCommand {
+    warnings: Warnings,
+}
+
+impl SplitRowCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("split row")
+            .desc("Splits strings into rows at a separator")
+            .required("separator", ArgType::String, "the text to split at")
+            .example(
+                "Split a comma separated list",
+                "echo a,b | split row ,",
+                vec![Value::String("a".into()), Value::String("b".into())],
+            )
+    }
+}
+
+impl PipelineElement for SplitRowCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let separator = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => match evaluated.get("separator") {
+                Some(Value::String(s)) if !s.is_empty() => s.clone(),
+                _ => {
+                    self.warnings
+                        .arg_error("split row", "the separator can't be empty");
+                    return Box::new(std::iter::empty());
+                }
+            },
+            Err(err) => {
+                self.warnings.arg_error("split row", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        Box::new(args.input.flat_map(move |x| {
+            match x {
+                Value::String(s) => s
+                    .split(separator.as_str())
+                    .map(|x| Value::String(x.to_string()))
+                    .collect(),
+                other => vec![other],
+            }
+        }))
+    }
+}
@@ -0,0 +1,78 @@
+use crate::*;
+use std::io::{BufRead, Stdin};
+
+// Streams the process's stdin, a line at a time, so output piped in from another
+// program flows through as it arrives. With --raw, the input is passed on in
+// chunks as it's read rather than split into lines.
+pub struct StdinCommand {
+    warnings: Warnings,
+}
+
+impl StdinCommand {
+    pub fn new(warnings: Warnings) -> Self {
+        Self { warnings }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("stdin")
+            .desc("Reads the process's stdin, a line at a time")
+            .side_effects()
+            .switch(
+                "raw",
+                Some('r'),
+                "output chunks as they're read instead of lines",
+            )
+    }
+}
+
+impl PipelineElement for StdinCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let raw = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated.has("raw"),
+            Err(err) => {
+                self.warnings.arg_error("stdin", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        Box::new(StdinIterator {
+            stdin: std::io::stdin(),
+            raw,
+        })
+    }
+}
+
+struct StdinIterator {
+    stdin: Stdin,
+    raw: bool,
+}
+
+impl Iterator for StdinIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut stdin = self.stdin.lock();
+
+        if self.raw {
+            let buffer = stdin.fill_buf().ok()?;
+            if buffer.is_empty() {
+                return None;
+            }
+
+            let st = String::from_utf8_lossy(buffer).to_string();
+            let length = buffer.len();
+            stdin.consume(length);
+            Some(Value::String(st))
+        } else {
+            let mut line = String::new();
+            match stdin.read_line(&mut line) {
+                Ok(0) | Err(_) => None,
+                Ok(_) => {
+                    let len = line.trim_end_matches(&['\r', '\n'][..]).len();
+                    line.truncate(len);
+                    Some(Value::String(line))
+                }
+            }
+        }
+    }
+}
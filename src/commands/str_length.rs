@@ -3,6 +3,18 @@ use num_bigint::BigInt;
 
 pub struct StrLengthCommand;
 
+impl StrLengthCommand {
+    pub fn signature() -> Signature {
+        Signature::build("str length")
+            .desc("Outputs the length of each string")
+            .example(
+                "Get the length of a string",
+                "echo hello | str length",
+                vec![Value::BigInt(5.into())],
+            )
+    }
+}
+
 impl PipelineElement for StrLengthCommand {
     fn start(&self, args: CommandArgs) -> ValueIterator {
         Box::new(args.input.map(|x| match x {
@@ -2,6 +2,18 @@ use crate::*;
 
 pub struct SumCommand;
 
+impl SumCommand {
+    pub fn signature() -> Signature {
+        Signature::build("sum")
+            .desc("Adds up all the values in the stream")
+            .example(
+                "Add up the first five numbers",
+                "count | take 5 | sum",
+                vec![Value::SmallInt(10)],
+            )
+    }
+}
+
 impl PipelineElement for SumCommand {
     fn start(&self, args: CommandArgs) -> ValueIterator {
         Box::new(SumIterator {
@@ -2,6 +2,19 @@ use crate::*;
 
 pub struct TakeCommand;
 
+impl TakeCommand {
+    pub fn signature() -> Signature {
+        Signature::build("take")
+            .desc("Outputs only the first values of the stream")
+            .required("n", ArgType::Int, "how many values to keep")
+            .example(
+                "Keep the first two values",
+                "echo 1 2 3 | take 2",
+                vec![Value::SmallInt(1), Value::SmallInt(2)],
+            )
+    }
+}
+
 impl PipelineElement for TakeCommand {
     fn start(&self, args: CommandArgs) -> ValueIterator {
         if let Value::SmallInt(n) = &args.args[0] {
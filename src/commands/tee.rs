@@ -0,0 +1,35 @@
+use crate::*;
+
+use crate::tee_adapter::TeeIterator;
+
+// Passes its input through unchanged while also feeding a copy of it to the
+// branch. Any arguments given to tee are handed to the branch.
+pub struct TeeCommand {
+    branch: Box<dyn PipelineElement>,
+}
+
+impl TeeCommand {
+    pub fn new(branch: Box<dyn PipelineElement>) -> Self {
+        Self { branch }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("tee")
+            .desc("Passes values through while also sending them to a branch")
+            .rest("args", ArgType::Any, "arguments for the branch")
+    }
+}
+
+impl PipelineElement for TeeCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let branch_args = args.args;
+
+        Box::new(TeeIterator::new(args.input, |input| {
+            self.branch.start(CommandArgs {
+                input,
+                args: branch_args,
+                state: State,
+            })
+        }))
+    }
+}
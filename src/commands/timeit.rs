@@ -0,0 +1,87 @@
+use crate::*;
+
+use std::sync::Arc;
+use std::time::Instant;
+
+// Runs the block the given number of times, draining all of its output each
+// time, and outputs how long each run took in nanoseconds. Pipe them to
+// `format duration` to read them as `1sec 250ms`.
+pub struct TimeitCommand {
+    warnings: Warnings,
+    block: Arc<dyn PipelineElement + Send + Sync>,
+}
+
+impl TimeitCommand {
+    pub fn new(warnings: Warnings, block: impl PipelineElement + Send + Sync + 'static) -> Self {
+        Self {
+            warnings,
+            block: Arc::new(block),
+        }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("timeit")
+            .desc("Outputs how long each run of a block takes, in nanoseconds")
+            .required("runs", ArgType::Int, "how many times to run the block")
+            .rest("args", ArgType::Any, "arguments for the block")
+    }
+}
+
+impl PipelineElement for TimeitCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.warnings.arg_error("timeit", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let runs = match evaluated.get("runs") {
+            Some(Value::SmallInt(n)) if *n >= 0 => *n,
+            _ => {
+                self.warnings
+                    .arg_error("timeit", "`runs` can't be negative");
+                return Box::new(std::iter::empty());
+            }
+        };
+        let block_args = evaluated.rest().to_vec();
+
+        Box::new(TimeitIterator {
+            block: self.block.clone(),
+            args: block_args,
+            runs_left: runs,
+        })
+    }
+}
+
+struct TimeitIterator {
+    block: Arc<dyn PipelineElement + Send + Sync>,
+    args: Vec<Value>,
+    runs_left: i64,
+}
+
+impl Iterator for TimeitIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.runs_left <= 0 {
+            return None;
+        }
+        self.runs_left -= 1;
+
+        // Starting the block is part of the run, since some commands do their
+        // work there
+        let start = Instant::now();
+        self.block
+            .start(CommandArgs {
+                input: Box::new(std::iter::empty()),
+                args: self.args.clone(),
+                state: State,
+            })
+            .for_each(drop);
+        let elapsed = start.elapsed().as_nanos();
+
+        Some(Value::SmallInt(elapsed as i64))
+    }
+}
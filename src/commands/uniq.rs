@@ -0,0 +1,27 @@
+use std::collections::BTreeSet;
+
+use crate::*;
+
+// Removes repeated values from the stream, keeping the first of each. Values
+// are output as they arrive, but every distinct value is remembered.
+pub struct UniqCommand;
+
+impl UniqCommand {
+    pub fn signature() -> Signature {
+        Signature::build("uniq")
+            .desc("Removes repeated values")
+            .example(
+                "Remove repeats",
+                "echo 1 2 1 3 2 | uniq",
+                vec![Value::SmallInt(1), Value::SmallInt(2), Value::SmallInt(3)],
+            )
+    }
+}
+
+impl PipelineElement for UniqCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let mut seen = BTreeSet::new();
+
+        Box::new(args.input.filter(move |x| seen.insert(OrdValue(x.clone()))))
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::*;
+
+const DEFAULT_INTERVAL_MS: i64 = 500;
+const DEFAULT_DEBOUNCE_MS: i64 = 100;
+
+// Watches a file, or the entries of a directory, and runs the block each time
+// something changes, outputting what the block outputs. The block gets the
+// changed paths as its input. Changes that come close together are gathered
+// into one run, so saving several files at once only runs the block once.
+//
+// Changes are found by checking modification times every interval, so the
+// stream never ends on its own; something downstream (like take) has to stop it.
+pub struct WatchCommand {
+    cwd: Arc<WorkingDir>,
+    warnings: Warnings,
+    block: Arc<dyn PipelineElement + Send + Sync>,
+}
+
+impl WatchCommand {
+    pub fn new(
+        cwd: Arc<WorkingDir>,
+        warnings: Warnings,
+        block: impl PipelineElement + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            cwd,
+            warnings,
+            block: Arc::new(block),
+        }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("watch")
+            .desc("Runs a block each time a path changes")
+            .required("path", ArgType::String, "the file or directory to watch")
+            .named(
+                "interval",
+                Some('i'),
+                ArgType::Int,
+                "how often to check for changes, in milliseconds (500 by default)",
+            )
+            .named(
+                "debounce",
+                Some('d'),
+                ArgType::Int,
+                "how long things have to stay unchanged before the block runs, in milliseconds (100 by default)",
+            )
+            .rest("args", ArgType::Any, "arguments for the block")
+    }
+}
+
+impl PipelineElement for WatchCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let evaluated = match EvaluatedArgs::new(&Self::signature(), args.args) {
+            Ok(evaluated) => evaluated,
+            Err(err) => {
+                self.warnings.arg_error("watch", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        let path = match evaluated.get("path") {
+            Some(Value::String(path)) => self.cwd.resolve(path),
+            _ => return Box::new(std::iter::empty()),
+        };
+        let millis = |name, default| match evaluated.get(name) {
+            Some(Value::SmallInt(ms)) if *ms >= 0 => Ok(Duration::from_millis(*ms as u64)),
+            Some(_) => Err(format!("`--{}` can't be negative", name)),
+            None => Ok(Duration::from_millis(default as u64)),
+        };
+        let (interval, debounce) = match (
+            millis("interval", DEFAULT_INTERVAL_MS),
+            millis("debounce", DEFAULT_DEBOUNCE_MS),
+        ) {
+            (Ok(interval), Ok(debounce)) => (interval, debounce),
+            (Err(err), _) | (_, Err(err)) => {
+                self.warnings.arg_error("watch", err);
+                return Box::new(std::iter::empty());
+            }
+        };
+
+        Box::new(WatchIterator {
+            block: self.block.clone(),
+            block_args: evaluated.rest().to_vec(),
+            seen: scan(&path),
+            path,
+            interval,
+            debounce,
+            current: None,
+        })
+    }
+}
+
+struct WatchIterator {
+    block: Arc<dyn PipelineElement + Send + Sync>,
+    block_args: Vec<Value>,
+    path: PathBuf,
+    interval: Duration,
+    debounce: Duration,
+    // The modification times as of the last check
+    seen: HashMap<PathBuf, Option<SystemTime>>,
+    // The output of the block's latest run
+    current: Option<ValueIterator>,
+}
+
+impl WatchIterator {
+    // Waits for something to change, then for things to settle, returning every
+    // path that changed along the way
+    fn wait_for_changes(&mut self) -> BTreeSet<PathBuf> {
+        let mut changed = BTreeSet::new();
+        let mut last_change = Instant::now();
+
+        loop {
+            std::thread::sleep(self.interval);
+
+            let now = scan(&self.path);
+            let before = changed.len();
+            for (path, modified) in &now {
+                if self.seen.get(path) != Some(modified) {
+                    changed.insert(path.clone());
+                }
+            }
+            for path in self.seen.keys() {
+                if !now.contains_key(path) {
+                    changed.insert(path.clone());
+                }
+            }
+            self.seen = now;
+
+            if changed.len() > before {
+                last_change = Instant::now();
+            } else if !changed.is_empty() && last_change.elapsed() >= self.debounce {
+                return changed;
+            }
+        }
+    }
+}
+
+impl Iterator for WatchIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(current) = &mut self.current {
+                if let Some(value) = current.next() {
+                    return Some(value);
+                }
+            }
+
+            let changed: Vec<_> = self
+                .wait_for_changes()
+                .into_iter()
+                .map(|x| Value::String(x.to_string_lossy().to_string()))
+                .collect();
+
+            self.current = Some(self.block.start(CommandArgs {
+                input: Box::new(changed.into_iter()),
+                args: self.block_args.clone(),
+                state: State,
+            }));
+        }
+    }
+}
+
+// The modification times of the path, or of the entries in it if it's a
+// directory. A path that's missing is seen as having no entries.
+fn scan(path: &Path) -> HashMap<PathBuf, Option<SystemTime>> {
+    let mut times = HashMap::new();
+
+    if path.is_dir() {
+        if let Ok(entries) = std::fs::read_dir(path) {
+            for entry in entries.flatten() {
+                let modified = entry.metadata().and_then(|x| x.modified()).ok();
+                times.insert(entry.path(), modified);
+            }
+        }
+    } else if let Ok(metadata) = std::fs::metadata(path) {
+        times.insert(path.to_path_buf(), metadata.modified().ok());
+    }
+
+    times
+}
@@ -2,6 +2,19 @@ use crate::*;
 
 pub struct WhereCommand;
 
+impl WhereCommand {
+    pub fn signature() -> Signature {
+        Signature::build("where")
+            .desc("Keeps only the values greater than the given one")
+            .required("value", ArgType::Any, "the value to compare against")
+            .example(
+                "Keep the values greater than 2",
+                "echo 1 2 3 4 | where 2",
+                vec![Value::SmallInt(3), Value::SmallInt(4)],
+            )
+    }
+}
+
 impl PipelineElement for WhereCommand {
     fn start(&self, args: CommandArgs) -> ValueIterator {
         Box::new(WhereIterator {
@@ -21,7 +34,7 @@ impl Iterator for WhereIterator {
 
     fn next(&mut self) -> Option<Self::Item> {
         while let Some(next) = self.input.next() {
-            if self.pred.lt(&next) {
+            if compare_values(&self.pred, &next) == std::cmp::Ordering::Less {
                 return Some(next);
             }
         }
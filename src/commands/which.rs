@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+
+use crate::*;
+
+// Reports how a name would resolve when used as a command. Pipelines use the
+// longest registered name made of the first words, so this also lists any
+// shorter commands that name shadows. It works from the commands registered
+// when it was created, plus itself, so it should be added last.
+pub struct WhichCommand {
+    names: HashSet<String>,
+}
+
+impl WhichCommand {
+    pub fn new(registry: &CommandRegistry) -> Self {
+        let mut names: HashSet<_> = registry
+            .signatures()
+            .into_iter()
+            .map(|x| x.name.clone())
+            .collect();
+        names.insert(Self::signature().name);
+
+        Self { names }
+    }
+
+    pub fn signature() -> Signature {
+        Signature::build("which")
+            .desc("Shows which command a name runs")
+            .rest("name", ArgType::String, "the name to look up")
+            .example(
+                "Look up a subcommand",
+                "which str length",
+                vec![Value::String(
+                    "`str length` runs the internal command `str length`".into(),
+                )],
+            )
+    }
+}
+
+impl PipelineElement for WhichCommand {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        let words: Vec<_> = args
+            .args
+            .iter()
+            .filter_map(|x| match x {
+                Value::String(s) => Some(s.as_str()),
+                _ => None,
+            })
+            .flat_map(|x| x.split_whitespace())
+            .collect();
+        let name = words.join(" ");
+
+        let mut matches = (1..=words.len())
+            .rev()
+            .map(|len| (len, words[..len].join(" ")))
+            .filter(|(_, x)| self.names.contains(x));
+
+        let mut lines = vec![];
+
+        match matches.next() {
+            Some((len, command)) => {
+                lines.push(format!(
+                    "`{}` runs the internal command `{}`",
+                    name, command
+                ));
+
+                if len < words.len() {
+                    lines.push(format!(
+                        "  `{}` is passed as arguments",
+                        words[len..].join(" ")
+                    ));
+                }
+
+                for (_, shadowed) in matches {
+                    lines.push(format!("  this shadows `{}`", shadowed));
+                }
+            }
+            None => lines.push(format!("`{}` doesn't match any command", name)),
+        }
+
+        Box::new(lines.into_iter().map(Value::String))
+    }
+}
@@ -0,0 +1,193 @@
+use std::ops::Range;
+
+use crate::*;
+
+// A suggestion for the word being typed. The span is the part of the source the
+// suggestion replaces.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Completion {
+    pub span: Range<usize>,
+    pub text: String,
+}
+
+// Suggests what could go at the offset in a partly typed pipeline, like
+// `echo 1 | ta`. The source doesn't need to be complete or valid: only the
+// stage being typed is looked at, and only up to the offset. Suggestions are
+// command and subcommand names, flags from the command's signature, and file
+// paths for arguments that look like paths, relative to the working directory.
+pub fn complete_at(
+    source: &str,
+    offset: usize,
+    lookup: &CommandRegistry,
+    cwd: &WorkingDir,
+) -> Vec<Completion> {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    let (words, current_start) = scan_stage(&source[..offset]);
+    let current = &source[current_start..offset];
+
+    let mut candidates = vec![];
+
+    // The words typed so far might be the start of a longer command name
+    let parent = words.join(" ");
+    for signature in lookup.signatures() {
+        let name = match parent.as_str() {
+            "" => Some(signature.name.as_str()),
+            parent => signature
+                .name
+                .strip_prefix(parent)
+                .and_then(|x| x.strip_prefix(' ')),
+        };
+
+        if let Some(next) = name.and_then(|x| x.split(' ').next()) {
+            if next.starts_with(current) {
+                candidates.push(next.to_string());
+            }
+        }
+    }
+
+    if let Some(name_len) = lookup.longest_match(&words) {
+        let name = words[..name_len].join(" ");
+
+        if let Some(signature) = lookup.signature(&name) {
+            if current.starts_with('-') {
+                for flag in &signature.flags {
+                    let long = format!("--{}", flag.name);
+                    if long.starts_with(current) {
+                        candidates.push(long);
+                    }
+                }
+            } else {
+                let positional = words[name_len..]
+                    .iter()
+                    .filter(|x| !x.starts_with('-'))
+                    .count();
+                let takes_path = signature
+                    .positional
+                    .get(positional)
+                    .is_some_and(|x| x.name == "path");
+
+                if takes_path || looks_like_path(current) {
+                    candidates.extend(complete_path(current, cwd));
+                }
+            }
+        }
+    }
+
+    candidates.sort();
+    candidates.dedup();
+
+    candidates
+        .into_iter()
+        .map(|text| Completion {
+            span: current_start..offset,
+            text,
+        })
+        .collect()
+}
+
+// Finds the words of the last stage in the source, along with where the word
+// being typed starts. The word being typed is left out of the words, and is
+// empty if the source ends in a space.
+fn scan_stage(source: &str) -> (Vec<String>, usize) {
+    let mut words = vec![];
+    let mut word_start = None;
+    let mut in_quote = false;
+
+    for (idx, c) in source.char_indices() {
+        match c {
+            '"' => {
+                in_quote = !in_quote;
+                word_start.get_or_insert(idx);
+            }
+            '|' if !in_quote => {
+                words.clear();
+                word_start = None;
+            }
+            c if c.is_whitespace() && !in_quote => {
+                if let Some(start) = word_start.take() {
+                    words.push(source[start..idx].trim_matches('"').to_string());
+                }
+            }
+            _ => {
+                word_start.get_or_insert(idx);
+            }
+        }
+    }
+
+    (words, word_start.unwrap_or(source.len()))
+}
+
+fn looks_like_path(word: &str) -> bool {
+    word.contains('/') || word.starts_with('.') || word.starts_with('~')
+}
+
+// The entries of the directory being typed whose names start with what's been
+// typed of the last part, keeping the directory as typed. Directories end with
+// a `/` so completion can carry on into them.
+fn complete_path(word: &str, cwd: &WorkingDir) -> Vec<String> {
+    let (dir, prefix) = match word.rfind('/') {
+        Some(idx) => (&word[..=idx], &word[idx + 1..]),
+        None => ("", word),
+    };
+
+    let search = cwd.resolve(dir);
+
+    let entries = match std::fs::read_dir(search) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            // Hidden files only show up once a `.` has been typed
+            if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                return None;
+            }
+
+            let is_dir = entry.file_type().map(|x| x.is_dir()).unwrap_or(false);
+            Some(format!("{}{}{}", dir, name, if is_dir { "/" } else { "" }))
+        })
+        .collect()
+}
+
+// Whether the source is ready to run, or is still missing something, like a
+// closing quote or bracket or the stage after a trailing `|`. A REPL can keep
+// reading lines until this is true. Stray closing brackets don't make more
+// input wanted, so they count as complete and are left to fail when run.
+pub fn is_input_complete(source: &str) -> bool {
+    let mut open = vec![];
+    let mut quote = None;
+    let mut escaped = false;
+
+    for c in source.chars() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' && q == '"' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match c {
+            '"' | '\'' | '`' => quote = Some(c),
+            '(' => open.push(')'),
+            '[' => open.push(']'),
+            '{' => open.push('}'),
+            ')' | ']' | '}' if open.last() == Some(&c) => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+
+    quote.is_none() && open.is_empty() && !source.trim_end().ends_with('|')
+}
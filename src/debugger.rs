@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use crate::*;
+
+// Called as a pipeline built with `build_debug_pipeline` runs. Stages are
+// numbered from 0 in pipeline order. Since values are pulled through the
+// pipeline, a hook that blocks pauses the whole pipeline, which is how a host
+// can step through it one value at a time.
+pub trait DebuggerHook: Send + Sync {
+    // The stage is about to be started
+    fn on_enter(&self, _stage: usize, _call: &CallInfo) {}
+
+    fn on_value(&self, _stage: usize, _value: &Value) {}
+
+    // The stage has no more values
+    fn on_leave(&self, _stage: usize) {}
+
+    // A `breakpoint` in the pipeline is about to pass on a value. The pipeline
+    // stays paused until this returns.
+    fn on_breakpoint(&self, _label: &str, _value: &Value) {}
+}
+
+pub(crate) struct DebugIterator {
+    pub(crate) input: ValueIterator,
+    pub(crate) stage: usize,
+    pub(crate) debugger: Arc<dyn DebuggerHook>,
+    pub(crate) done: bool,
+}
+
+impl Iterator for DebugIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.input.next() {
+            Some(value) => {
+                self.debugger.on_value(self.stage, &value);
+                Some(value)
+            }
+            None => {
+                self.done = true;
+                self.debugger.on_leave(self.stage);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::Value;
+use crate::ValueIterator;
+
+type Work = Box<dyn FnOnce() -> ValueIterator + Send + Sync>;
+
+// Puts off a command's work until its output is first pulled on. start() runs
+// while the pipeline is being built, which can be well before it runs, or when
+// it never runs at all, like in an explain.
+pub struct DeferredIterator {
+    work: Option<Work>,
+    output: Option<ValueIterator>,
+}
+
+impl DeferredIterator {
+    pub fn new(work: impl FnOnce() -> ValueIterator + Send + Sync + 'static) -> Self {
+        Self {
+            work: Some(Box::new(work)),
+            output: None,
+        }
+    }
+}
+
+impl Iterator for DeferredIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(work) = self.work.take() {
+            self.output = Some(work());
+        }
+
+        self.output.as_mut()?.next()
+    }
+}
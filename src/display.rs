@@ -0,0 +1,86 @@
+use crate::*;
+
+// The text form of a value, as it's shown to users
+pub fn format_value(value: &Value) -> String {
+    match value {
+        Value::SmallInt(x) => x.to_string(),
+        Value::BigInt(x) => x.to_string(),
+        Value::String(s) => s.clone(),
+        x => format!("{:?}", x),
+    }
+}
+
+const DURATION_UNITS: &[(&str, i64)] = &[
+    ("wk", 7 * 24 * 60 * 60 * 1_000_000_000),
+    ("day", 24 * 60 * 60 * 1_000_000_000),
+    ("hr", 60 * 60 * 1_000_000_000),
+    ("min", 60 * 1_000_000_000),
+    ("sec", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+
+// A duration in nanoseconds (as timeit outputs them), written the way people
+// read them, like `2min 3sec`. Units that would be zero are left out.
+pub fn format_duration(nanos: i64) -> String {
+    if nanos == 0 {
+        return "0sec".into();
+    }
+
+    let mut remaining = nanos.unsigned_abs();
+    let mut parts = vec![];
+    for (unit, size) in DURATION_UNITS {
+        let count = remaining / *size as u64;
+        if count > 0 {
+            parts.push(format!("{}{}", count, unit));
+            remaining %= *size as u64;
+        }
+    }
+
+    let text = parts.join(" ");
+    if nanos < 0 {
+        format!("-{}", text)
+    } else {
+        text
+    }
+}
+
+// Reads a duration written like format_duration writes them, back into
+// nanoseconds. Parts can have fractions (`1.5sec`) and spaces between the number
+// and the unit, and `µs` works as well as `us`.
+pub fn parse_duration(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+
+    let mut total: i64 = 0;
+    let mut rest = text.trim_start();
+    if rest.is_empty() {
+        return None;
+    }
+
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = rest[number_len..].trim_start();
+
+        let unit_len = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = match &rest[..unit_len] {
+            "µs" => "us",
+            unit => unit,
+        };
+        let size = DURATION_UNITS.iter().find(|(x, _)| *x == unit)?.1;
+        rest = rest[unit_len..].trim_start();
+
+        total = total.checked_add((number * size as f64).round() as i64)?;
+    }
+
+    Some(if negative { -total } else { total })
+}
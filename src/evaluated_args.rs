@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use crate::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArgError {
+    UnknownFlag(String),
+    AmbiguousFlag(String),
+    MissingFlagValue(String),
+    UnexpectedFlagValue(String),
+    MissingPositional(String),
+    UnexpectedArgument(usize),
+    TypeMismatch {
+        name: String,
+        expected: ArgType,
+        found: Value,
+    },
+}
+
+impl std::fmt::Display for ArgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgError::UnknownFlag(flag) => write!(f, "unknown flag `{}`", flag),
+            ArgError::AmbiguousFlag(flag) => write!(f, "`{}` matches more than one flag", flag),
+            ArgError::MissingFlagValue(flag) => write!(f, "flag `--{}` needs a value", flag),
+            ArgError::UnexpectedFlagValue(flag) => {
+                write!(f, "flag `--{}` doesn't take a value", flag)
+            }
+            ArgError::MissingPositional(name) => write!(f, "missing argument `{}`", name),
+            ArgError::UnexpectedArgument(index) => {
+                write!(f, "unexpected argument at position {}", index)
+            }
+            ArgError::TypeMismatch {
+                name,
+                expected,
+                found,
+            } => write!(f, "`{}` should be {:?}, found {:?}", name, expected, found),
+        }
+    }
+}
+
+// A command's arguments matched up against its signature. Flags are string
+// arguments starting with `-`, and can be given anywhere among the positionals:
+//
+//   --name        long flags, or any unambiguous prefix of one (--std for --stderr)
+//   --name=value  a flag and its value in one argument
+//   -n            short flags
+//   -abc          several short flags at once; only the last can take a value
+//   --            ends the flags, so everything after it is a positional
+//
+// Flags that take a value otherwise use the argument after them. Everything
+// else fills the positionals in order.
+//
+// Pipelines check every stage's arguments this way before starting anything,
+// and report a mismatch as a StageError, so commands only see an error here
+// when they're started directly. They report it, along with values they can't
+// use like a negative count, through whatever they report other problems
+// through: the host's stderr for print and friends that talk to the host, and
+// warnings for everything else.
+#[derive(Clone, Debug, Default)]
+pub struct EvaluatedArgs {
+    values: HashMap<String, Value>,
+    switches: Vec<String>,
+    rest: Vec<Value>,
+}
+
+impl EvaluatedArgs {
+    pub fn new(signature: &Signature, args: Vec<Value>) -> Result<Self, ArgError> {
+        let mut evaluated = EvaluatedArgs::default();
+        let mut positional = vec![];
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let text = match &arg {
+                Value::String(s) if is_flag(s) => s.clone(),
+                _ => {
+                    positional.push(arg);
+                    continue;
+                }
+            };
+
+            if text == "--" {
+                positional.extend(args.by_ref());
+                break;
+            }
+
+            if let Some(long) = text.strip_prefix("--") {
+                let (name, inline_value) = match long.find('=') {
+                    Some(idx) => (&long[..idx], Some(Value::String(long[idx + 1..].into()))),
+                    None => (long, None),
+                };
+
+                let flag = find_long_flag(signature, name)?;
+                evaluated.set_flag(flag, inline_value, &mut args)?;
+            } else {
+                let shorts: Vec<char> = text[1..].chars().collect();
+
+                for (idx, short) in shorts.iter().enumerate() {
+                    let flag = signature
+                        .flags
+                        .iter()
+                        .find(|x| x.short == Some(*short))
+                        .ok_or_else(|| ArgError::UnknownFlag(format!("-{}", short)))?;
+
+                    if flag.arg_type.is_some() && idx + 1 < shorts.len() {
+                        return Err(ArgError::MissingFlagValue(flag.name.clone()));
+                    }
+
+                    evaluated.set_flag(flag, None, &mut args)?;
+                }
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        for param in &signature.positional {
+            match positional.next() {
+                Some(value) => {
+                    let value = coerce(&param.name, param.arg_type, value)?;
+                    evaluated.values.insert(param.name.clone(), value);
+                }
+                None if param.optional => {
+                    if let Some(default) = &param.default {
+                        evaluated.values.insert(param.name.clone(), default.clone());
+                    }
+                }
+                None => return Err(ArgError::MissingPositional(param.name.clone())),
+            }
+        }
+
+        for (index, value) in (signature.positional.len()..).zip(positional) {
+            match &signature.rest {
+                Some(rest) => {
+                    let value = coerce(&rest.name, rest.arg_type, value)?;
+                    evaluated.rest.push(value);
+                }
+                None => return Err(ArgError::UnexpectedArgument(index)),
+            }
+        }
+
+        Ok(evaluated)
+    }
+
+    fn set_flag(
+        &mut self,
+        flag: &Flag,
+        inline_value: Option<Value>,
+        args: &mut impl Iterator<Item = Value>,
+    ) -> Result<(), ArgError> {
+        match flag.arg_type {
+            Some(arg_type) => {
+                let value = inline_value
+                    .or_else(|| args.next())
+                    .ok_or_else(|| ArgError::MissingFlagValue(flag.name.clone()))?;
+                let value = coerce(&flag.name, arg_type, value)?;
+                self.values.insert(flag.name.clone(), value);
+            }
+            None if inline_value.is_some() => {
+                return Err(ArgError::UnexpectedFlagValue(flag.name.clone()));
+            }
+            None => self.switches.push(flag.name.clone()),
+        }
+
+        Ok(())
+    }
+
+    // The value of a positional or a flag that takes a value
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.values.get(name)
+    }
+
+    // Whether a switch was given
+    pub fn has(&self, name: &str) -> bool {
+        self.switches.iter().any(|x| x == name)
+    }
+
+    pub fn rest(&self) -> &[Value] {
+        &self.rest
+    }
+}
+
+// Negative numbers, like -1 or -.5, and a lone `-` are positionals, not flags.
+// Only a digit or `.` after the `-` makes a number, so short flags that happen
+// to spell `-inf` or `-nan` are still flags.
+fn is_flag(s: &str) -> bool {
+    match s.strip_prefix('-').and_then(|x| x.chars().next()) {
+        Some(c) => !c.is_ascii_digit() && c != '.',
+        None => false,
+    }
+}
+
+fn find_long_flag<'a>(signature: &'a Signature, name: &str) -> Result<&'a Flag, ArgError> {
+    if let Some(flag) = signature.flags.iter().find(|x| x.name == name) {
+        return Ok(flag);
+    }
+
+    let mut matches = signature.flags.iter().filter(|x| x.name.starts_with(name));
+    match (matches.next(), matches.next()) {
+        (Some(flag), None) if !name.is_empty() => Ok(flag),
+        (Some(_), Some(_)) => Err(ArgError::AmbiguousFlag(format!("--{}", name))),
+        _ => Err(ArgError::UnknownFlag(format!("--{}", name))),
+    }
+}
+
+fn coerce(name: &str, arg_type: ArgType, value: Value) -> Result<Value, ArgError> {
+    let coerced = match (arg_type, &value) {
+        (ArgType::Any, _) => Some(value.clone()),
+        (ArgType::Int, Value::SmallInt(_)) | (ArgType::Int, Value::BigInt(_)) => {
+            Some(value.clone())
+        }
+        (ArgType::Int, Value::String(s)) => s.trim().parse::<i64>().ok().map(Value::SmallInt),
+        (ArgType::String, Value::String(_)) => Some(value.clone()),
+        (ArgType::String, Value::SmallInt(x)) => Some(Value::String(x.to_string())),
+        (ArgType::String, Value::BigInt(x)) => Some(Value::String(x.to_string())),
+        _ => None,
+    };
+
+    coerced.ok_or_else(|| ArgError::TypeMismatch {
+        name: name.into(),
+        expected: arg_type,
+        found: value,
+    })
+}
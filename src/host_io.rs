@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, Write};
+use std::sync::Mutex;
+
+// How commands talk to whoever is hosting the engine. Commands that need to read
+// from or write to the user go through this rather than the process's stdin and
+// stdout, so hosts that aren't a terminal can supply their own.
+pub trait HostIo: Send + Sync {
+    // Shows the prompt and reads one line of input, without the line ending.
+    // Returns None once there's no more input.
+    fn read_line(&self, prompt: &str, suppress_output: bool) -> Option<String>;
+
+    fn write_out(&self, text: &str);
+
+    fn write_err(&self, text: &str);
+
+    // Puts text on the host's clipboard, returning whether it could. Hosts
+    // without a clipboard, like the plain terminal host, can leave these out.
+    fn set_clipboard(&self, _text: &str) -> bool {
+        false
+    }
+
+    fn get_clipboard(&self) -> Option<String> {
+        None
+    }
+
+    // Lets the user know something happened, like a long pipeline finishing,
+    // through whatever the host has for that: a desktop notification, a webhook,
+    // a log. By default it's written to stderr.
+    fn notify(&self, title: &str, body: &str) {
+        if body.is_empty() {
+            self.write_err(&format!("{}\n", title));
+        } else {
+            self.write_err(&format!("{}: {}\n", title, body));
+        }
+    }
+}
+
+// Reads from the process's stdin. Note that this can't turn off terminal echo,
+// so input read with suppress_output will still be visible as it's typed.
+pub struct StdHostIo;
+
+impl HostIo for StdHostIo {
+    fn read_line(&self, prompt: &str, _suppress_output: bool) -> Option<String> {
+        let mut stdout = std::io::stdout();
+        let _ = write!(stdout, "{}", prompt);
+        let _ = stdout.flush();
+
+        let mut line = String::new();
+        match std::io::stdin().lock().read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => {
+                let len = line.trim_end_matches(&['\r', '\n'][..]).len();
+                line.truncate(len);
+                Some(line)
+            }
+        }
+    }
+
+    fn write_out(&self, text: &str) {
+        print!("{}", text);
+    }
+
+    fn write_err(&self, text: &str) {
+        eprint!("{}", text);
+    }
+}
+
+// Captures everything written to it instead of printing, and answers reads from
+// a queue of lines given up front. Lets library users and tests run commands
+// and then check what they would have printed.
+#[derive(Default)]
+pub struct CaptureHostIo {
+    input: Mutex<VecDeque<String>>,
+    stdout: Mutex<String>,
+    stderr: Mutex<String>,
+    clipboard: Mutex<Option<String>>,
+}
+
+impl CaptureHostIo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_input(lines: Vec<String>) -> Self {
+        Self {
+            input: Mutex::new(lines.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn stdout(&self) -> String {
+        self.stdout.lock().unwrap().clone()
+    }
+
+    pub fn stderr(&self) -> String {
+        self.stderr.lock().unwrap().clone()
+    }
+}
+
+impl HostIo for CaptureHostIo {
+    fn read_line(&self, prompt: &str, _suppress_output: bool) -> Option<String> {
+        self.write_out(prompt);
+        self.input.lock().unwrap().pop_front()
+    }
+
+    fn write_out(&self, text: &str) {
+        self.stdout.lock().unwrap().push_str(text);
+    }
+
+    fn write_err(&self, text: &str) {
+        self.stderr.lock().unwrap().push_str(text);
+    }
+
+    fn set_clipboard(&self, text: &str) -> bool {
+        *self.clipboard.lock().unwrap() = Some(text.into());
+        true
+    }
+
+    fn get_clipboard(&self) -> Option<String> {
+        self.clipboard.lock().unwrap().clone()
+    }
+}
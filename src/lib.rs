@@ -1,7 +1,71 @@
 mod commands;
 pub use commands::*;
 
+mod deferred_adapter;
+mod merge_adapter;
 mod par_iter_adapter;
+mod tee_adapter;
 
 mod data;
 pub use data::*;
+
+mod builtins;
+pub use builtins::*;
+
+mod completion;
+pub use completion::*;
+
+mod debugger;
+pub use debugger::*;
+
+mod display;
+pub use display::*;
+
+mod evaluated_args;
+pub use evaluated_args::*;
+
+mod host_io;
+pub use host_io::*;
+
+mod limits;
+pub use limits::*;
+
+#[cfg(feature = "msgpack")]
+mod msgpack;
+#[cfg(feature = "msgpack")]
+pub use msgpack::*;
+
+mod nuon;
+pub use nuon::*;
+
+mod ordering;
+pub use ordering::*;
+
+mod permissions;
+pub use permissions::*;
+
+mod pipeline;
+pub use pipeline::*;
+
+mod preview;
+pub use preview::*;
+
+mod prompt;
+pub use prompt::*;
+
+mod random;
+pub use random::*;
+
+mod registry;
+pub use registry::*;
+
+mod signature;
+pub use signature::*;
+
+mod warnings;
+pub use warnings::*;
+
+mod working_dir;
+pub use working_dir::WorkingDir;
+
+pub mod testing;
@@ -0,0 +1,270 @@
+use std::sync::mpsc::{sync_channel, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::*;
+
+// How many values the pipeline's thread can get ahead of the run when watching
+// for stalls
+const STALL_BACKLOG: usize = 64;
+
+// The shortest wait between checks on a pipeline running on its own thread, so
+// very short (or zero) limits don't have the checks spin
+const MIN_POLL: Duration = Duration::from_millis(10);
+
+// Limits on a single run of a pipeline, for running snippets we don't trust.
+// Limits are checked as each value comes out of the pipeline, so a stage that
+// spins without ever producing a value can still run past the time limit,
+// unless the run goes through run_pipeline_with_limits.
+#[derive(Clone, Debug, Default)]
+pub struct Limits {
+    pub max_duration: Option<Duration>,
+    pub max_values: Option<usize>,
+    // An approximate budget, in bytes, for the values the run outputs
+    pub max_memory: Option<usize>,
+    // How long a pipeline can go without outputting anything before the run is
+    // stopped, blaming the stage that's holding it up. Only checked by
+    // run_pipeline_with_limits.
+    pub stall_timeout: Option<Duration>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LimitExceeded {
+    Duration(Duration),
+    Values(usize),
+    Memory(usize),
+    Stalled {
+        stage: usize,
+        command: String,
+        timeout: Duration,
+    },
+}
+
+impl std::fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitExceeded::Duration(limit) => write!(f, "run took longer than {}", human(*limit)),
+            LimitExceeded::Values(limit) => write!(f, "run output more than {} values", limit),
+            LimitExceeded::Memory(limit) => write!(f, "run output more than {} bytes", limit),
+            LimitExceeded::Stalled {
+                stage,
+                command,
+                timeout,
+            } => write!(
+                f,
+                "stage {} (`{}`) output nothing for {}",
+                stage,
+                command,
+                human(*timeout)
+            ),
+        }
+    }
+}
+
+fn human(duration: Duration) -> String {
+    format_duration(duration.as_nanos().min(i64::MAX as u128) as i64)
+}
+
+// Why a run of a pipeline didn't finish
+#[derive(Clone, Debug, PartialEq)]
+pub enum RunError {
+    Stage(StageError),
+    Limit(LimitExceeded),
+}
+
+impl std::fmt::Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::Stage(err) => err.fmt(f),
+            RunError::Limit(err) => err.fmt(f),
+        }
+    }
+}
+
+impl From<StageError> for RunError {
+    fn from(err: StageError) -> Self {
+        RunError::Stage(err)
+    }
+}
+
+impl From<LimitExceeded> for RunError {
+    fn from(err: LimitExceeded) -> Self {
+        RunError::Limit(err)
+    }
+}
+
+pub fn run_with_limits(
+    pipeline: ValueIterator,
+    limits: &Limits,
+) -> Result<Vec<Value>, LimitExceeded> {
+    collect_with_limits(pipeline, limits)
+}
+
+// Builds and runs the pipeline within the limits. With a time limit or a stall
+// timeout, the pipeline is started and run on its own thread, so a stage that
+// hangs, like one waiting on the network, can be caught even while it's being
+// started. A stage that's stuck can't be interrupted, so its thread is left to
+// finish on its own and stops at its next value.
+pub fn run_pipeline_with_limits(
+    pipeline: Vec<CallInfo>,
+    lookup: &CommandRegistry,
+    limits: &Limits,
+) -> Result<Vec<Value>, RunError> {
+    let poll = match (limits.stall_timeout, limits.max_duration) {
+        (Some(a), Some(b)) => a.min(b).max(MIN_POLL),
+        (Some(x), None) | (None, Some(x)) => x.max(MIN_POLL),
+        (None, None) => {
+            return Ok(collect_with_limits(
+                build_pipeline(pipeline, lookup)?,
+                limits,
+            )?)
+        }
+    };
+
+    let watch = Arc::new(StallWatch::new(&pipeline));
+    let debugger: Option<Arc<dyn DebuggerHook>> = match limits.stall_timeout {
+        Some(_) => Some(watch.clone()),
+        None => None,
+    };
+    let pipeline = prepare_pipeline(pipeline, lookup, None)?;
+
+    let start = Instant::now();
+    let (sender, receiver) = sync_channel(STALL_BACKLOG);
+    std::thread::spawn(move || {
+        for value in start_pipeline(pipeline, debugger) {
+            if sender.send(value).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut stopped = None;
+    let values = std::iter::from_fn(|| loop {
+        match receiver.recv_timeout(poll) {
+            Ok(value) => return Some(value),
+            Err(RecvTimeoutError::Disconnected) => return None,
+            // Nothing came out, but that's fine as long as the stages are still
+            // busy, like a sum working through a long stream, and there's time left
+            Err(RecvTimeoutError::Timeout) => {
+                if let Some(timeout) = limits.stall_timeout {
+                    if let Some(err) = watch.stalled(timeout) {
+                        stopped = Some(err);
+                        return None;
+                    }
+                }
+                if let Some(max_duration) = limits.max_duration {
+                    if start.elapsed() > max_duration {
+                        stopped = Some(LimitExceeded::Duration(max_duration));
+                        return None;
+                    }
+                }
+            }
+        }
+    });
+
+    let output = collect_with_limits(values, limits);
+
+    match stopped {
+        Some(err) => Err(err.into()),
+        None => Ok(output?),
+    }
+}
+
+fn collect_with_limits(
+    pipeline: impl Iterator<Item = Value>,
+    limits: &Limits,
+) -> Result<Vec<Value>, LimitExceeded> {
+    let start = Instant::now();
+    let mut memory = 0;
+    let mut output = vec![];
+
+    for value in pipeline {
+        if let Some(max_duration) = limits.max_duration {
+            if start.elapsed() > max_duration {
+                return Err(LimitExceeded::Duration(max_duration));
+            }
+        }
+
+        if let Some(max_values) = limits.max_values {
+            if output.len() >= max_values {
+                return Err(LimitExceeded::Values(max_values));
+            }
+        }
+
+        if let Some(max_memory) = limits.max_memory {
+            memory += approximate_size(&value);
+            if memory > max_memory {
+                return Err(LimitExceeded::Memory(max_memory));
+            }
+        }
+
+        output.push(value);
+    }
+
+    Ok(output)
+}
+
+fn approximate_size(value: &Value) -> usize {
+    let heap = match value {
+        Value::BigInt(x) => (x.bits() as usize).div_ceil(8),
+        Value::String(s) => s.len(),
+        _ => 0,
+    };
+
+    std::mem::size_of::<Value>() + heap
+}
+
+// Tracks which stage the pipeline is working in. Values are pulled through
+// the pipeline, so after a stage outputs a value (or finishes), the work moves
+// on to the stage after it until something else comes out.
+struct StallWatch {
+    commands: Vec<String>,
+    last: Mutex<(Option<usize>, Instant)>,
+}
+
+impl StallWatch {
+    fn new(pipeline: &[CallInfo]) -> Self {
+        Self {
+            commands: pipeline.iter().map(|call| call.name.clone()).collect(),
+            last: Mutex::new((None, Instant::now())),
+        }
+    }
+
+    fn record(&self, stage: usize) {
+        *self.last.lock().unwrap() = (Some(stage), Instant::now());
+    }
+
+    // The stage that's been working for the whole timeout without outputting
+    // anything, if there is one
+    fn stalled(&self, timeout: Duration) -> Option<LimitExceeded> {
+        let (last_stage, at) = *self.last.lock().unwrap();
+        if at.elapsed() < timeout {
+            return None;
+        }
+
+        let stage = last_stage.map_or(0, |x| x + 1);
+        self.commands
+            .get(stage)
+            .map(|command| LimitExceeded::Stalled {
+                stage,
+                command: command.clone(),
+                timeout,
+            })
+    }
+}
+
+impl DebuggerHook for StallWatch {
+    // Stages are started in order, so one that hangs while starting is the one
+    // entered last
+    fn on_enter(&self, stage: usize, _call: &CallInfo) {
+        *self.last.lock().unwrap() = (stage.checked_sub(1), Instant::now());
+    }
+
+    fn on_value(&self, stage: usize, _value: &Value) {
+        self.record(stage);
+    }
+
+    fn on_leave(&self, stage: usize) {
+        self.record(stage);
+    }
+}
@@ -1,50 +1,22 @@
-use std::collections::HashMap;
+use std::sync::Arc;
 
 use enginep::*;
 
-struct CallInfo {
-    name: String,
-    args: Vec<Value>,
-}
-
-fn build_pipeline(
-    pipeline: Vec<CallInfo>,
-    lookup: HashMap<String, Box<dyn PipelineElement>>,
-) -> ValueIterator {
-    let mut prev: ValueIterator = Box::new(std::iter::empty());
-
-    for elem in pipeline.into_iter() {
-        if let Some(command) = lookup.get(&elem.name) {
-            prev = command.start(CommandArgs {
-                input: prev,
-                args: elem.args,
-                state: State,
-            })
-        }
-    }
-
-    prev
-}
-
 fn main() {
     let count = match std::env::args().skip(1).next() {
         Some(x) => x.parse::<i64>().unwrap(),
         None => 1000000,
     };
 
-    let mut map = HashMap::new();
-    map.insert("count".into(), command(CountCommand));
-    map.insert("take".into(), command(TakeCommand));
-    map.insert("sum".into(), command(SumCommand));
-    map.insert("append".into(), command(AppendCommand));
-    map.insert("prepend".into(), command(PrependCommand));
-    map.insert("where".into(), command(WhereCommand));
-    map.insert("length".into(), command(LengthCommand));
-    map.insert("par-each".into(), command(ParEachCommand));
-    map.insert("inspect".into(), command(InspectCommand));
-    map.insert("contains".into(), command(ContainsCommand));
-    map.insert("open".into(), command(OpenCommand));
-    map.insert("str length".into(), command(StrLengthCommand));
+    let warnings = Warnings::new();
+    let map = Builtins {
+        io: Arc::new(StdHostIo),
+        cwd: Arc::new(WorkingDir::from_process()),
+        warnings: warnings.clone(),
+        random: Arc::new(RandomSource::new()),
+        permissions: Arc::new(Permissions::all()),
+    }
+    .registry();
 
     let pipeline = vec![
         CallInfo {
@@ -96,7 +68,13 @@ fn main() {
         // },
     ];
 
-    let pipeline = build_pipeline(pipeline, map);
+    let pipeline = match build_pipeline_with_warnings(pipeline, &map, &warnings) {
+        Ok(pipeline) => pipeline,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return;
+        }
+    };
 
     {
         // use rayon::prelude::*;
@@ -113,4 +91,8 @@ fn main() {
         let _: Vec<_> = pipeline.collect();
         // println!("{:?}", result);
     }
+
+    for warning in warnings.take() {
+        eprintln!("warning: {}", warning);
+    }
 }
@@ -0,0 +1,46 @@
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::sync::Mutex;
+
+use crate::Value;
+use crate::ValueIterator;
+
+// How many values each source can get ahead of whoever is pulling on the
+// merged stream before it waits
+const SOURCE_BACKLOG: usize = 64;
+
+// Merges several streams into one. Each source is drained on its own thread, and
+// values come out in whatever order they arrive, so the merged stream only ends
+// once every source has.
+pub struct MergeIterator {
+    receiver: Mutex<Receiver<Value>>,
+}
+
+impl MergeIterator {
+    pub fn new(sources: Vec<ValueIterator>) -> Self {
+        let (sender, receiver) = sync_channel(SOURCE_BACKLOG);
+
+        for source in sources {
+            let sender = sender.clone();
+            std::thread::spawn(move || {
+                for value in source {
+                    if sender.send(value).is_err() {
+                        // The merged stream was dropped, so stop pulling on this one
+                        break;
+                    }
+                }
+            });
+        }
+
+        Self {
+            receiver: Mutex::new(receiver),
+        }
+    }
+}
+
+impl Iterator for MergeIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.get_mut().unwrap().recv().ok()
+    }
+}
@@ -0,0 +1,169 @@
+use std::convert::TryFrom;
+
+use num_bigint::BigInt;
+
+use crate::*;
+
+// A MessagePack encoding of values, for exchanging them with other processes.
+// Ints use the smallest encoding that fits them. MessagePack has no big ints,
+// so those are written as an extension holding their big-endian two's
+// complement bytes.
+const BIGINT_EXT: i8 = 1;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MsgpackError {
+    UnexpectedEnd,
+    // A MessagePack type with no matching kind of value, by its first byte
+    Unsupported(u8),
+    InvalidUtf8,
+}
+
+impl std::fmt::Display for MsgpackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MsgpackError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            MsgpackError::Unsupported(marker) => {
+                write!(f, "unsupported MessagePack type 0x{:02x}", marker)
+            }
+            MsgpackError::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+        }
+    }
+}
+
+// The encoded bytes for a value, or None for kinds of value it can't encode yet
+pub fn to_msgpack(value: &Value) -> Option<Vec<u8>> {
+    let mut output = vec![];
+
+    match value {
+        Value::SmallInt(x) => write_int(*x, &mut output),
+        Value::BigInt(x) => {
+            let bytes = x.to_signed_bytes_be();
+            write_len(bytes.len(), [0xc7, 0xc8, 0xc9], &mut output);
+            output.push(BIGINT_EXT as u8);
+            output.extend_from_slice(&bytes);
+        }
+        Value::String(s) => {
+            if s.len() < 32 {
+                output.push(0xa0 | s.len() as u8);
+            } else {
+                write_len(s.len(), [0xd9, 0xda, 0xdb], &mut output);
+            }
+            output.extend_from_slice(s.as_bytes());
+        }
+        _ => return None,
+    }
+
+    Some(output)
+}
+
+// Decodes the value at the start of the input, returning it along with how many
+// bytes it used, so that values written one after another can be read back in turn
+pub fn from_msgpack(input: &[u8]) -> Result<(Value, usize), MsgpackError> {
+    let marker = *input.first().ok_or(MsgpackError::UnexpectedEnd)?;
+    let take = |start: usize, len: usize| -> Result<&[u8], MsgpackError> {
+        input
+            .get(start..start + len)
+            .ok_or(MsgpackError::UnexpectedEnd)
+    };
+    let uint = |len: usize| -> Result<u64, MsgpackError> {
+        Ok(take(1, len)?
+            .iter()
+            .fold(0u64, |acc, x| acc << 8 | *x as u64))
+    };
+
+    let value = match marker {
+        0x00..=0x7f => (Value::SmallInt(marker as i64), 1),
+        0xe0..=0xff => (Value::SmallInt(marker as i8 as i64), 1),
+        0xcc..=0xcf => {
+            let len = 1 << (marker - 0xcc);
+            let x = uint(len)?;
+            let value = match i64::try_from(x) {
+                Ok(x) => Value::SmallInt(x),
+                Err(_) => Value::BigInt(BigInt::from(x)),
+            };
+            (value, 1 + len)
+        }
+        0xd0..=0xd3 => {
+            let len = 1 << (marker - 0xd0);
+            // Sign extend from the encoded width
+            let shift = 64 - 8 * len as u32;
+            let x = ((uint(len)? << shift) as i64) >> shift;
+            (Value::SmallInt(x), 1 + len)
+        }
+        0xa0..=0xbf => read_str(input, 1, (marker & 0x1f) as usize)?,
+        0xd9..=0xdb => {
+            let size = 1 << (marker - 0xd9);
+            read_str(input, 1 + size, uint(size)? as usize)?
+        }
+        0xc7..=0xc9 => {
+            let size = 1 << (marker - 0xc7);
+            let len = uint(size)? as usize;
+            if take(1 + size, 1)?[0] as i8 != BIGINT_EXT {
+                return Err(MsgpackError::Unsupported(marker));
+            }
+            let bytes = take(2 + size, len)?;
+            (
+                Value::BigInt(BigInt::from_signed_bytes_be(bytes)),
+                2 + size + len,
+            )
+        }
+        _ => return Err(MsgpackError::Unsupported(marker)),
+    };
+
+    Ok(value)
+}
+
+fn read_str(input: &[u8], start: usize, len: usize) -> Result<(Value, usize), MsgpackError> {
+    let bytes = input
+        .get(start..start + len)
+        .ok_or(MsgpackError::UnexpectedEnd)?;
+    let s = std::str::from_utf8(bytes).map_err(|_| MsgpackError::InvalidUtf8)?;
+
+    Ok((Value::String(s.into()), start + len))
+}
+
+fn write_int(x: i64, output: &mut Vec<u8>) {
+    match x {
+        0..=127 => output.push(x as u8),
+        -32..=-1 => output.push(x as i8 as u8),
+        128..=0xff => output.extend_from_slice(&[0xcc, x as u8]),
+        0x100..=0xffff => {
+            output.push(0xcd);
+            output.extend_from_slice(&(x as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            output.push(0xce);
+            output.extend_from_slice(&(x as u32).to_be_bytes());
+        }
+        0x1_0000_0000.. => {
+            output.push(0xcf);
+            output.extend_from_slice(&(x as u64).to_be_bytes());
+        }
+        -0x80..=-33 => output.extend_from_slice(&[0xd0, x as i8 as u8]),
+        -0x8000..=-0x81 => {
+            output.push(0xd1);
+            output.extend_from_slice(&(x as i16).to_be_bytes());
+        }
+        -0x8000_0000..=-0x8001 => {
+            output.push(0xd2);
+            output.extend_from_slice(&(x as i32).to_be_bytes());
+        }
+        _ => {
+            output.push(0xd3);
+            output.extend_from_slice(&x.to_be_bytes());
+        }
+    }
+}
+
+// Writes a length using the 8, 16 or 32 bit form, given their markers
+fn write_len(len: usize, markers: [u8; 3], output: &mut Vec<u8>) {
+    if len <= 0xff {
+        output.extend_from_slice(&[markers[0], len as u8]);
+    } else if len <= 0xffff {
+        output.push(markers[1]);
+        output.extend_from_slice(&(len as u16).to_be_bytes());
+    } else {
+        output.push(markers[2]);
+        output.extend_from_slice(&(len as u32).to_be_bytes());
+    }
+}
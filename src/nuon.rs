@@ -0,0 +1,170 @@
+use num_bigint::BigInt;
+
+use crate::*;
+
+// NUON is the text form of values that reads back as the same value: ints are
+// written as digits and strings are quoted, with JSON-style escapes. Ints that
+// fit in 64 bits read back as small ints, and larger ones as big ints.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NuonError {
+    UnexpectedEnd,
+    UnexpectedChar(usize, char),
+    InvalidEscape(usize),
+    TrailingInput(usize),
+}
+
+impl std::fmt::Display for NuonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NuonError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            NuonError::UnexpectedChar(pos, c) => {
+                write!(f, "unexpected `{}` at position {}", c, pos)
+            }
+            NuonError::InvalidEscape(pos) => write!(f, "invalid escape at position {}", pos),
+            NuonError::TrailingInput(pos) => {
+                write!(f, "unexpected input after the value at position {}", pos)
+            }
+        }
+    }
+}
+
+// The NUON text for a value, or None for kinds of value it can't write yet
+pub fn to_nuon(value: &Value) -> Option<String> {
+    match value {
+        Value::SmallInt(x) => Some(x.to_string()),
+        Value::BigInt(x) => Some(x.to_string()),
+        Value::String(s) => {
+            let mut output = String::with_capacity(s.len() + 2);
+            output.push('"');
+            for c in s.chars() {
+                match c {
+                    '"' => output.push_str("\\\""),
+                    '\\' => output.push_str("\\\\"),
+                    '\n' => output.push_str("\\n"),
+                    '\r' => output.push_str("\\r"),
+                    '\t' => output.push_str("\\t"),
+                    c if c.is_control() => output.push_str(&format!("\\u{:04x}", c as u32)),
+                    c => output.push(c),
+                }
+            }
+            output.push('"');
+            Some(output)
+        }
+        _ => None,
+    }
+}
+
+pub fn from_nuon(source: &str) -> Result<Value, NuonError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut pos = skip_whitespace(&chars, 0);
+
+    let value = match chars.get(pos) {
+        Some('"') => {
+            let (s, end) = parse_string(&chars, pos)?;
+            pos = end;
+            Value::String(s)
+        }
+        Some(c) if *c == '-' || c.is_ascii_digit() => {
+            let start = pos;
+            pos += 1;
+            while pos < chars.len() && chars[pos].is_ascii_digit() {
+                pos += 1;
+            }
+
+            let digits: String = chars[start..pos].iter().collect();
+            match digits.parse::<i64>() {
+                Ok(x) => Value::SmallInt(x),
+                Err(_) => match digits.parse::<BigInt>() {
+                    Ok(x) => Value::BigInt(x),
+                    Err(_) => return Err(NuonError::UnexpectedChar(start, chars[start])),
+                },
+            }
+        }
+        Some(c) => return Err(NuonError::UnexpectedChar(pos, *c)),
+        None => return Err(NuonError::UnexpectedEnd),
+    };
+
+    let pos = skip_whitespace(&chars, pos);
+    if pos < chars.len() {
+        return Err(NuonError::TrailingInput(pos));
+    }
+
+    Ok(value)
+}
+
+fn skip_whitespace(chars: &[char], mut pos: usize) -> usize {
+    while pos < chars.len() && chars[pos].is_whitespace() {
+        pos += 1;
+    }
+    pos
+}
+
+// Parses the quoted string starting at `start`, returning it along with the
+// position just past the closing quote
+fn parse_string(chars: &[char], start: usize) -> Result<(String, usize), NuonError> {
+    let mut output = String::new();
+    let mut pos = start + 1;
+
+    loop {
+        match chars.get(pos) {
+            Some('"') => return Ok((output, pos + 1)),
+            Some('\\') => {
+                let escape = pos;
+                let c = match chars.get(pos + 1) {
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some('/') => '/',
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    Some('u') => {
+                        let (c, end) = parse_unicode_escape(chars, escape)?;
+                        output.push(c);
+                        pos = end;
+                        continue;
+                    }
+                    Some(_) => return Err(NuonError::InvalidEscape(escape)),
+                    None => return Err(NuonError::UnexpectedEnd),
+                };
+                output.push(c);
+                pos += 2;
+            }
+            Some(c) => {
+                output.push(*c);
+                pos += 1;
+            }
+            None => return Err(NuonError::UnexpectedEnd),
+        }
+    }
+}
+
+// Parses a `\uXXXX` escape, or a pair of them for characters outside the basic
+// plane, returning the character and the position after the escape
+fn parse_unicode_escape(chars: &[char], start: usize) -> Result<(char, usize), NuonError> {
+    let unit = |pos: usize| -> Result<u32, NuonError> {
+        if chars.get(pos) != Some(&'\\') || chars.get(pos + 1) != Some(&'u') {
+            return Err(NuonError::InvalidEscape(pos));
+        }
+        let hex: String = chars
+            .get(pos + 2..pos + 6)
+            .ok_or(NuonError::UnexpectedEnd)?
+            .iter()
+            .collect();
+        u32::from_str_radix(&hex, 16).map_err(|_| NuonError::InvalidEscape(pos))
+    };
+
+    let first = unit(start)?;
+    if (0xd800..0xdc00).contains(&first) {
+        let second = unit(start + 6)?;
+        if !(0xdc00..0xe000).contains(&second) {
+            return Err(NuonError::InvalidEscape(start + 6));
+        }
+        let code = 0x10000 + ((first - 0xd800) << 10) + (second - 0xdc00);
+        let c = std::char::from_u32(code).ok_or(NuonError::InvalidEscape(start))?;
+        return Ok((c, start + 12));
+    }
+
+    let c = std::char::from_u32(first).ok_or(NuonError::InvalidEscape(start))?;
+    Ok((c, start + 6))
+}
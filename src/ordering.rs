@@ -0,0 +1,60 @@
+use std::cmp::Ordering;
+
+use num_bigint::BigInt;
+
+use crate::*;
+
+// The one ordering of values that sorting, deduplicating and comparing
+// commands all share, so they agree with each other. It's total: numbers sort
+// before strings, and small and big ints compare by their numeric value, so
+// `SmallInt(2)` and `BigInt(2)` are equal.
+pub fn compare_values(lhs: &Value, rhs: &Value) -> Ordering {
+    match (lhs, rhs) {
+        (Value::SmallInt(a), Value::SmallInt(b)) => a.cmp(b),
+        (Value::SmallInt(a), Value::BigInt(b)) => BigInt::from(*a).cmp(b),
+        (Value::BigInt(a), Value::SmallInt(b)) => a.cmp(&BigInt::from(*b)),
+        (Value::BigInt(a), Value::BigInt(b)) => a.cmp(b),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => match rank(lhs).cmp(&rank(rhs)) {
+            // Kinds of value this doesn't know the order of yet fall back on
+            // their own ordering, or failing that, how they print
+            Ordering::Equal => lhs
+                .partial_cmp(rhs)
+                .unwrap_or_else(|| format!("{:?}", lhs).cmp(&format!("{:?}", rhs))),
+            ordering => ordering,
+        },
+    }
+}
+
+fn rank(value: &Value) -> u8 {
+    match value {
+        Value::SmallInt(_) | Value::BigInt(_) => 0,
+        Value::String(_) => 1,
+        _ => 2,
+    }
+}
+
+// A value ordered by compare_values, so it can be used in sorted collections
+// like BTreeSet
+#[derive(Clone, Debug)]
+pub struct OrdValue(pub Value);
+
+impl PartialEq for OrdValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OrdValue {}
+
+impl PartialOrd for OrdValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrdValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_values(&self.0, &other.0)
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Capability {
+    Filesystem,
+    Network,
+    ProcessSpawn,
+    EnvWrite,
+}
+
+impl std::fmt::Display for Capability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Capability::Filesystem => write!(f, "filesystem"),
+            Capability::Network => write!(f, "network"),
+            Capability::ProcessSpawn => write!(f, "process-spawn"),
+            Capability::EnvWrite => write!(f, "env-write"),
+        }
+    }
+}
+
+// Which capabilities commands are allowed to use. Hosts running scripts they
+// don't trust can turn off, say, filesystem access and register commands through
+// `guard` so that they do nothing but warn when their capability is denied.
+#[derive(Clone, Debug)]
+pub struct Permissions {
+    allowed: HashSet<Capability>,
+}
+
+impl Permissions {
+    pub fn all() -> Self {
+        Self {
+            allowed: vec![
+                Capability::Filesystem,
+                Capability::Network,
+                Capability::ProcessSpawn,
+                Capability::EnvWrite,
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    pub fn none() -> Self {
+        Self {
+            allowed: HashSet::new(),
+        }
+    }
+
+    pub fn allow(mut self, capability: Capability) -> Self {
+        self.allowed.insert(capability);
+        self
+    }
+
+    pub fn deny(mut self, capability: Capability) -> Self {
+        self.allowed.remove(&capability);
+        self
+    }
+
+    pub fn allows(&self, capability: Capability) -> bool {
+        self.allowed.contains(&capability)
+    }
+
+    // Wraps a command that needs the given capability. If the capability is
+    // denied, the command outputs nothing instead of running, and warns that it
+    // was stopped, naming the command and the capability.
+    pub fn guard(
+        self: &Arc<Self>,
+        capability: Capability,
+        name: &str,
+        warnings: &Warnings,
+        command: impl PipelineElement + Send + Sync + 'static,
+    ) -> impl PipelineElement + Send + Sync + 'static {
+        GuardedCommand {
+            permissions: self.clone(),
+            capability,
+            name: name.into(),
+            warnings: warnings.clone(),
+            command,
+        }
+    }
+}
+
+struct GuardedCommand<C> {
+    permissions: Arc<Permissions>,
+    capability: Capability,
+    name: String,
+    warnings: Warnings,
+    command: C,
+}
+
+impl<C: PipelineElement> PipelineElement for GuardedCommand<C> {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        if self.permissions.allows(self.capability) {
+            self.command.start(args)
+        } else {
+            self.warnings.push(Warning::new(format!(
+                "{}: needs {} access, which is denied",
+                self.name, self.capability
+            )));
+            Box::new(std::iter::empty())
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use std::sync::Arc;
+
+use crate::debugger::DebugIterator;
+use crate::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallInfo {
+    pub name: String,
+    pub args: Vec<Value>,
+}
+
+// A stage whose arguments don't fit its command's signature. Pipelines are
+// checked before any stage starts, so commands can count on getting what their
+// signature asks for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StageError {
+    pub stage: usize,
+    pub command: String,
+    pub error: ArgError,
+}
+
+impl std::fmt::Display for StageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "stage {} (`{}`): {}",
+            self.stage, self.command, self.error
+        )
+    }
+}
+
+pub fn build_pipeline(
+    pipeline: Vec<CallInfo>,
+    lookup: &CommandRegistry,
+) -> Result<ValueIterator, StageError> {
+    build(pipeline, lookup, None, None)
+}
+
+// Like build_pipeline, but warns about deprecated commands in the pipeline
+pub fn build_pipeline_with_warnings(
+    pipeline: Vec<CallInfo>,
+    lookup: &CommandRegistry,
+    warnings: &Warnings,
+) -> Result<ValueIterator, StageError> {
+    build(pipeline, lookup, None, Some(warnings))
+}
+
+// Like build_pipeline, but commands with side effects aren't run. In their
+// place, the stage outputs a line saying what would have been run, and
+// later stages get that line as their input.
+pub fn build_dry_run_pipeline(
+    pipeline: Vec<CallInfo>,
+    lookup: &CommandRegistry,
+) -> Result<ValueIterator, StageError> {
+    let mut pipeline = prepare_pipeline(pipeline, lookup, None)?;
+
+    for stage in &mut pipeline {
+        if !stage.side_effects {
+            continue;
+        }
+
+        let plan = match &stage.command {
+            Some((_, args)) => std::iter::once(stage.name.clone())
+                .chain(args.iter().map(format_value))
+                .collect::<Vec<_>>()
+                .join(" "),
+            None => continue,
+        };
+        stage.command = Some((Arc::new(PlannedCommand { plan }), vec![]));
+    }
+
+    Ok(start_pipeline(pipeline, None))
+}
+
+// Like build_pipeline, but calls the debugger as each stage runs
+pub fn build_debug_pipeline(
+    pipeline: Vec<CallInfo>,
+    lookup: &CommandRegistry,
+    debugger: Arc<dyn DebuggerHook>,
+) -> Result<ValueIterator, StageError> {
+    build(pipeline, lookup, Some(debugger), None)
+}
+
+fn build(
+    pipeline: Vec<CallInfo>,
+    lookup: &CommandRegistry,
+    debugger: Option<Arc<dyn DebuggerHook>>,
+    warnings: Option<&Warnings>,
+) -> Result<ValueIterator, StageError> {
+    let pipeline = prepare_pipeline(pipeline, lookup, warnings)?;
+    Ok(start_pipeline(pipeline, debugger))
+}
+
+// A stage with its command looked up, ready to start. Holding the command rather
+// than the registry lets the pipeline be started on another thread.
+pub(crate) struct PreparedStage {
+    call: CallInfo,
+    // The name the call resolved to
+    name: String,
+    command: Option<(Arc<dyn PipelineElement + Send + Sync>, Vec<Value>)>,
+    side_effects: bool,
+}
+
+pub(crate) fn prepare_pipeline(
+    pipeline: Vec<CallInfo>,
+    lookup: &CommandRegistry,
+    warnings: Option<&Warnings>,
+) -> Result<Vec<PreparedStage>, StageError> {
+    let mut prepared = vec![];
+
+    for (stage, elem) in pipeline.into_iter().enumerate() {
+        let call = resolve_call(elem.clone(), lookup);
+        let mut name = elem.name.clone();
+        let mut side_effects = false;
+        let command = match call {
+            Some(call) => {
+                if let Some(signature) = lookup.signature(&call.name) {
+                    if let Err(error) = EvaluatedArgs::new(signature, call.args.clone()) {
+                        return Err(StageError {
+                            stage,
+                            command: call.name,
+                            error,
+                        });
+                    }
+                    side_effects = signature.side_effects;
+                }
+                name = call.name.clone();
+
+                if let (Some(warnings), Some(replacement)) =
+                    (warnings, lookup.deprecation(&call.name))
+                {
+                    warnings.push(Warning::at_stage(
+                        stage,
+                        format!("`{}` is deprecated, use `{}`", call.name, replacement),
+                    ));
+                }
+
+                lookup
+                    .get_shared(&call.name)
+                    .map(|command| (command, call.args))
+            }
+            None => None,
+        };
+
+        prepared.push(PreparedStage {
+            call: elem,
+            name,
+            command,
+            side_effects,
+        });
+    }
+
+    Ok(prepared)
+}
+
+pub(crate) fn start_pipeline(
+    pipeline: Vec<PreparedStage>,
+    debugger: Option<Arc<dyn DebuggerHook>>,
+) -> ValueIterator {
+    let mut prev: ValueIterator = Box::new(std::iter::empty());
+
+    for (stage, elem) in pipeline.into_iter().enumerate() {
+        if let Some(debugger) = &debugger {
+            debugger.on_enter(stage, &elem.call);
+        }
+
+        if let Some((command, args)) = elem.command {
+            prev = command.start(CommandArgs {
+                input: prev,
+                args,
+                state: State,
+            })
+        }
+
+        if let Some(debugger) = &debugger {
+            prev = Box::new(DebugIterator {
+                input: prev,
+                stage,
+                debugger: debugger.clone(),
+                done: false,
+            });
+        }
+    }
+
+    prev
+}
+
+// Stands in for a command with side effects in a dry run
+struct PlannedCommand {
+    plan: String,
+}
+
+impl PipelineElement for PlannedCommand {
+    fn start(&self, _: CommandArgs) -> ValueIterator {
+        Box::new(std::iter::once(Value::String(format!(
+            "would run `{}`",
+            self.plan
+        ))))
+    }
+}
+
+// Finds the command for a call. If the call's name is longer than any registered
+// name, like `str length extra`, the words past the command's name become its
+// first arguments.
+pub fn resolve(
+    call: CallInfo,
+    lookup: &CommandRegistry,
+) -> Option<(&dyn PipelineElement, Vec<Value>)> {
+    let call = resolve_call(call, lookup)?;
+
+    lookup.get(&call.name).map(|command| (command, call.args))
+}
+
+// Rewrites a call to use the registered name it resolves to
+pub fn resolve_call(call: CallInfo, lookup: &CommandRegistry) -> Option<CallInfo> {
+    let words: Vec<_> = call.name.split_whitespace().collect();
+    let name_len = lookup.longest_match(&words)?;
+
+    let args = words[name_len..]
+        .iter()
+        .map(|x| Value::String(x.to_string()))
+        .chain(call.args)
+        .collect();
+
+    Some(CallInfo {
+        name: words[..name_len].join(" "),
+        args,
+    })
+}
+
+// How a stage of a pipeline would run, without running it
+#[derive(Clone, Debug, PartialEq)]
+pub struct StagePlan {
+    pub stage: usize,
+    pub call: CallInfo,
+    // The call as it resolves against the registry, or None if no command matched
+    pub resolved: Option<CallInfo>,
+    // Whether the arguments fit the command's signature
+    pub arg_error: Option<ArgError>,
+    // The command to use instead, if the resolved command is deprecated
+    pub deprecated: Option<String>,
+    // Whether the command changes something outside the pipeline
+    pub side_effects: bool,
+}
+
+impl std::fmt::Display for StagePlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.resolved {
+            Some(resolved) => {
+                write!(f, "{}: {} {:?}", self.stage, resolved.name, resolved.args)?;
+                if let Some(err) = &self.arg_error {
+                    write!(f, " (error: {})", err)?;
+                }
+                if let Some(replacement) = &self.deprecated {
+                    write!(f, " (deprecated, use `{}`)", replacement)?;
+                }
+                if self.side_effects {
+                    write!(f, " (side effects)")?;
+                }
+                Ok(())
+            }
+            None => write!(
+                f,
+                "{}: unknown command `{}` (skipped)",
+                self.stage, self.call.name
+            ),
+        }
+    }
+}
+
+pub fn explain_pipeline(pipeline: &[CallInfo], lookup: &CommandRegistry) -> Vec<StagePlan> {
+    pipeline
+        .iter()
+        .enumerate()
+        .map(|(stage, call)| {
+            let resolved = resolve_call(call.clone(), lookup);
+
+            let arg_error = resolved.as_ref().and_then(|resolved| {
+                let signature = lookup.signature(&resolved.name)?;
+                EvaluatedArgs::new(signature, resolved.args.clone()).err()
+            });
+            let deprecated = resolved
+                .as_ref()
+                .and_then(|resolved| lookup.deprecation(&resolved.name))
+                .map(String::from);
+            let side_effects = resolved
+                .as_ref()
+                .and_then(|resolved| lookup.signature(&resolved.name))
+                .is_some_and(|x| x.side_effects);
+
+            StagePlan {
+                stage,
+                call: call.clone(),
+                resolved,
+                arg_error,
+                deprecated,
+                side_effects,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,188 @@
+use std::sync::{Arc, Mutex};
+
+use crate::*;
+
+// Live preview of a pipeline as it's being edited. Each stage's output is tapped
+// as it flows into the next stage, so one run of the pipeline gives us the first
+// values of every stage without re-running the source once per stage.
+pub struct PipelinePreview {
+    limit: usize,
+    stages: Vec<CallInfo>,
+    cache: Vec<Vec<Value>>,
+    // Whether each stage's stream ended, rather than being cut short by a later
+    // stage that stopped pulling from it
+    ended: Vec<bool>,
+    // The last stage's stream, picking up after the values already previewed.
+    // Stages appended to the pipeline are fed from this, so previewing them
+    // doesn't re-run the stages before them.
+    tail: Option<ValueIterator>,
+}
+
+impl PipelinePreview {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            stages: vec![],
+            cache: vec![],
+            ended: vec![],
+            tail: None,
+        }
+    }
+
+    pub fn set_stages(&mut self, stages: Vec<CallInfo>) {
+        let unchanged = self
+            .stages
+            .iter()
+            .zip(stages.iter())
+            .take_while(|(old, new)| old == new)
+            .count();
+
+        if unchanged == self.stages.len() {
+            // Only appended to (or not changed at all), so everything we have
+            // is still good, and the new stages can carry on from the tail
+        } else if unchanged == stages.len() {
+            // Stages were removed from the end. The previews of the rest stay
+            // valid, unless a removed stage like `take` was cutting them short.
+            self.cache.truncate(unchanged);
+            self.ended.truncate(unchanged);
+            self.tail = None;
+
+            let limit = self.limit;
+            let complete = self
+                .cache
+                .iter()
+                .zip(&self.ended)
+                .all(|(values, ended)| *ended || values.len() >= limit);
+            if !complete {
+                self.clear();
+            }
+        } else {
+            self.clear();
+        }
+
+        self.stages = stages;
+    }
+
+    // Returns the first `limit` values seen after each stage. A stage may show
+    // fewer values than the limit if a later stage stopped pulling from it early.
+    // Stages with side effects, and those after them, show nothing.
+    pub fn preview(&mut self, lookup: &CommandRegistry) -> &[Vec<Value>] {
+        if self.cache.len() != self.stages.len() {
+            match (self.cache.last(), self.tail.take()) {
+                // The tail has already output what the last preview shows, so
+                // that goes first
+                (Some(last), Some(tail)) => {
+                    let input = Box::new(last.clone().into_iter().chain(tail));
+                    self.run(self.cache.len(), input, lookup);
+                }
+                _ => {
+                    self.clear();
+                    self.run(0, Box::new(std::iter::empty()), lookup);
+                }
+            }
+        }
+
+        &self.cache
+    }
+
+    fn clear(&mut self) {
+        self.cache.clear();
+        self.ended.clear();
+        self.tail = None;
+    }
+
+    // Runs the stages from `first` on, with the given input, adding their
+    // previews to the cache
+    fn run(&mut self, first: usize, input: ValueIterator, lookup: &CommandRegistry) {
+        let mut taps = vec![];
+        let mut prev = input;
+        let mut blocked = false;
+
+        for elem in self.stages[first..].iter() {
+            // Stages with side effects, like `rm` or `input`, aren't run just
+            // because they were typed, so neither they nor anything after them
+            // has a preview
+            if has_side_effects(elem, lookup) {
+                blocked = true;
+                break;
+            }
+
+            let tap = Arc::new(Mutex::new(Tap::default()));
+
+            // Stages still being typed often don't have their arguments yet.
+            // Like unknown commands, they're skipped until they do.
+            if let Some((command, args)) = resolve_checked(elem.clone(), lookup) {
+                prev = command.start(CommandArgs {
+                    input: prev,
+                    args,
+                    state: State,
+                });
+            }
+
+            prev = Box::new(TapIterator {
+                input: prev,
+                tap: tap.clone(),
+                limit: self.limit,
+            });
+            taps.push(tap);
+        }
+
+        for _ in prev.by_ref().take(self.limit) {}
+
+        for tap in taps {
+            let mut tap = tap.lock().unwrap();
+            self.cache.push(std::mem::take(&mut tap.values));
+            self.ended.push(tap.ended);
+        }
+        self.cache.resize(self.stages.len(), vec![]);
+        self.ended.resize(self.stages.len(), false);
+
+        self.tail = if blocked { None } else { Some(prev) };
+    }
+}
+
+fn has_side_effects(call: &CallInfo, lookup: &CommandRegistry) -> bool {
+    resolve_call(call.clone(), lookup)
+        .and_then(|call| lookup.signature(&call.name))
+        .is_some_and(|x| x.side_effects)
+}
+
+fn resolve_checked(
+    call: CallInfo,
+    lookup: &CommandRegistry,
+) -> Option<(&dyn PipelineElement, Vec<Value>)> {
+    let call = resolve_call(call, lookup)?;
+    let signature = lookup.signature(&call.name)?;
+    EvaluatedArgs::new(signature, call.args.clone()).ok()?;
+
+    lookup.get(&call.name).map(|command| (command, call.args))
+}
+
+#[derive(Default)]
+struct Tap {
+    values: Vec<Value>,
+    ended: bool,
+}
+
+struct TapIterator {
+    input: ValueIterator,
+    tap: Arc<Mutex<Tap>>,
+    limit: usize,
+}
+
+impl Iterator for TapIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.input.next();
+
+        let mut tap = self.tap.lock().unwrap();
+        match &next {
+            Some(value) if tap.values.len() < self.limit => tap.values.push(value.clone()),
+            Some(_) => {}
+            None => tap.ended = true,
+        }
+
+        next
+    }
+}
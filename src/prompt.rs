@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use crate::*;
+
+// Runs a pipeline written to draw a shell prompt and returns what it output,
+// joined into one string. Prompts run before every command a user types, so the
+// pipeline gets a small time budget, and a prompt that runs past it (or stalls)
+// gives None so the host can fall back to a plain prompt instead of hanging.
+pub fn render_prompt(
+    prompt: Vec<CallInfo>,
+    lookup: &CommandRegistry,
+    budget: Duration,
+) -> Option<String> {
+    let limits = Limits {
+        max_duration: Some(budget),
+        stall_timeout: Some(budget),
+        ..Limits::default()
+    };
+
+    let values = run_pipeline_with_limits(prompt, lookup, &limits).ok()?;
+
+    Some(values.iter().map(format_value).collect())
+}
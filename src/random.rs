@@ -0,0 +1,51 @@
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// A source of random numbers shared by the random commands. Hosts that need
+// repeatable output, like tests, can give it a fixed seed.
+//
+// This is splitmix64: fast and well distributed, but not for cryptographic use.
+pub struct RandomSource {
+    state: Mutex<u64>,
+}
+
+impl RandomSource {
+    pub fn new() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_nanos() as u64)
+            .unwrap_or_default();
+
+        Self::seeded(nanos)
+    }
+
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            state: Mutex::new(seed),
+        }
+    }
+
+    pub fn next_u64(&self) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        *state = state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // A number between min and max, including both
+    pub fn range(&self, min: i64, max: i64) -> i64 {
+        let span = (max as i128 - min as i128 + 1) as u128;
+        let offset = (self.next_u64() as u128 % span) as i128;
+
+        (min as i128 + offset) as i64
+    }
+}
+
+impl Default for RandomSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::*;
+
+struct RegisteredCommand {
+    signature: Signature,
+    command: Arc<dyn PipelineElement + Send + Sync>,
+    // The command to use instead, if this one is deprecated
+    replacement: Option<String>,
+}
+
+// The commands available to pipelines, by name, along with their signatures
+#[derive(Default)]
+pub struct CommandRegistry {
+    commands: HashMap<String, RegisteredCommand>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(
+        &mut self,
+        signature: Signature,
+        command: impl PipelineElement + Send + Sync + 'static,
+    ) {
+        self.commands.insert(
+            signature.name.clone(),
+            RegisteredCommand {
+                signature,
+                command: Arc::new(command),
+                replacement: None,
+            },
+        );
+    }
+
+    // Registers a command that still works but is on its way out, pointing users
+    // at the command to use instead. Renamed commands can be registered under
+    // both names, with the old one deprecated.
+    pub fn insert_deprecated(
+        &mut self,
+        signature: Signature,
+        command: impl PipelineElement + Send + Sync + 'static,
+        replacement: &str,
+    ) {
+        self.commands.insert(
+            signature.name.clone(),
+            RegisteredCommand {
+                signature,
+                command: Arc::new(command),
+                replacement: Some(replacement.into()),
+            },
+        );
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.commands.contains_key(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn PipelineElement> {
+        self.commands.get(name).map(|x| x.command.as_ref() as _)
+    }
+
+    // The command itself, rather than a borrow of it, for starting it on another
+    // thread than the one holding the registry
+    pub fn get_shared(&self, name: &str) -> Option<Arc<dyn PipelineElement + Send + Sync>> {
+        self.commands.get(name).map(|x| x.command.clone())
+    }
+
+    pub fn signature(&self, name: &str) -> Option<&Signature> {
+        self.commands.get(name).map(|x| &x.signature)
+    }
+
+    // The replacement for a deprecated command, or None if it isn't deprecated
+    pub fn deprecation(&self, name: &str) -> Option<&str> {
+        self.commands.get(name)?.replacement.as_deref()
+    }
+
+    // Subcommands are registered under their full name, like `str length`. This
+    // finds the longest registered name made up of the first words, returning how
+    // many words it used, so that `str length` wins over a plain `str`.
+    pub fn longest_match<S: AsRef<str>>(&self, words: &[S]) -> Option<usize> {
+        (1..=words.len()).rev().find(|len| {
+            let name: Vec<_> = words[..*len].iter().map(|x| x.as_ref()).collect();
+            self.contains(&name.join(" "))
+        })
+    }
+
+    // The commands directly under the given parent, sorted by name
+    pub fn subcommands(&self, parent: &str) -> Vec<&Signature> {
+        self.signatures()
+            .into_iter()
+            .filter(|x| is_subcommand(&x.name, parent))
+            .collect()
+    }
+
+    // All signatures, sorted by command name
+    pub fn signatures(&self) -> Vec<&Signature> {
+        let mut signatures: Vec<_> = self.commands.values().map(|x| &x.signature).collect();
+        signatures.sort_by(|a, b| a.name.cmp(&b.name));
+        signatures
+    }
+}
+
+pub(crate) fn is_subcommand(name: &str, parent: &str) -> bool {
+    match name.strip_prefix(parent) {
+        Some(rest) => rest.starts_with(' ') && !rest[1..].contains(' '),
+        None => false,
+    }
+}
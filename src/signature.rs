@@ -0,0 +1,161 @@
+use crate::Value;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArgType {
+    Int,
+    String,
+    Any,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Positional {
+    pub name: String,
+    pub arg_type: ArgType,
+    pub description: String,
+    pub optional: bool,
+    // Filled in when an optional positional isn't given
+    pub default: Option<Value>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Flag {
+    pub name: String,
+    pub short: Option<char>,
+    // None for switches, which don't take a value
+    pub arg_type: Option<ArgType>,
+    pub description: String,
+}
+
+// An example call, along with the values it's expected to output. Examples are
+// checked by running them, which keeps the docs from drifting out of date.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub description: String,
+    pub source: String,
+    pub expected: Vec<Value>,
+}
+
+// Describes how a command is called, so hosts can offer completion and help
+// without having to run anything
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signature {
+    pub name: String,
+    pub description: String,
+    pub positional: Vec<Positional>,
+    pub rest: Option<Positional>,
+    pub flags: Vec<Flag>,
+    pub examples: Vec<Example>,
+    // Whether running the command reaches outside the pipeline, like changing
+    // files on disk or reading from and writing to the terminal. Previews and
+    // dry runs don't run these.
+    pub side_effects: bool,
+}
+
+impl Signature {
+    pub fn build(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            description: String::new(),
+            positional: vec![],
+            rest: None,
+            flags: vec![],
+            examples: vec![],
+            side_effects: false,
+        }
+    }
+
+    pub fn desc(mut self, description: &str) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn side_effects(mut self) -> Self {
+        self.side_effects = true;
+        self
+    }
+
+    pub fn required(mut self, name: &str, arg_type: ArgType, description: &str) -> Self {
+        self.positional.push(Positional {
+            name: name.into(),
+            arg_type,
+            description: description.into(),
+            optional: false,
+            default: None,
+        });
+        self
+    }
+
+    pub fn optional(mut self, name: &str, arg_type: ArgType, description: &str) -> Self {
+        self.positional.push(Positional {
+            name: name.into(),
+            arg_type,
+            description: description.into(),
+            optional: true,
+            default: None,
+        });
+        self
+    }
+
+    pub fn optional_with_default(
+        mut self,
+        name: &str,
+        arg_type: ArgType,
+        default: Value,
+        description: &str,
+    ) -> Self {
+        self.positional.push(Positional {
+            name: name.into(),
+            arg_type,
+            description: description.into(),
+            optional: true,
+            default: Some(default),
+        });
+        self
+    }
+
+    pub fn rest(mut self, name: &str, arg_type: ArgType, description: &str) -> Self {
+        self.rest = Some(Positional {
+            name: name.into(),
+            arg_type,
+            description: description.into(),
+            optional: true,
+            default: None,
+        });
+        self
+    }
+
+    pub fn switch(mut self, name: &str, short: Option<char>, description: &str) -> Self {
+        self.flags.push(Flag {
+            name: name.into(),
+            short,
+            arg_type: None,
+            description: description.into(),
+        });
+        self
+    }
+
+    pub fn named(
+        mut self,
+        name: &str,
+        short: Option<char>,
+        arg_type: ArgType,
+        description: &str,
+    ) -> Self {
+        self.flags.push(Flag {
+            name: name.into(),
+            short,
+            arg_type: Some(arg_type),
+            description: description.into(),
+        });
+        self
+    }
+
+    pub fn example(mut self, description: &str, source: &str, expected: Vec<Value>) -> Self {
+        self.examples.push(Example {
+            description: description.into(),
+            source: source.into(),
+            expected,
+        });
+        self
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+
+use crate::Value;
+use crate::ValueIterator;
+
+// How many values the branch is allowed to fall behind the main stream before
+// the main stream waits for it to catch up
+const BRANCH_BACKLOG: usize = 1024;
+
+pub struct TeeIterator {
+    input: ValueIterator,
+    sender: Option<SyncSender<Value>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl TeeIterator {
+    pub fn new(input: ValueIterator, branch: impl FnOnce(ValueIterator) -> ValueIterator) -> Self {
+        let (sender, receiver) = sync_channel(BRANCH_BACKLOG);
+
+        let branch = branch(Box::new(ReceiverIterator {
+            receiver: Mutex::new(receiver),
+        }));
+
+        // The branch is drained on its own thread so that it sees every value
+        // without the main stream having to hold on to them
+        let worker = std::thread::spawn(move || branch.for_each(drop));
+
+        Self {
+            input,
+            sender: Some(sender),
+            worker: Some(worker),
+        }
+    }
+
+    fn finish(&mut self) {
+        self.sender = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Iterator for TeeIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.input.next() {
+            Some(x) => {
+                if let Some(sender) = &self.sender {
+                    if sender.send(x.clone()).is_err() {
+                        // The branch stopped listening, so stop feeding it
+                        self.sender = None;
+                    }
+                }
+                Some(x)
+            }
+            None => {
+                self.finish();
+                None
+            }
+        }
+    }
+}
+
+impl Drop for TeeIterator {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+struct ReceiverIterator {
+    receiver: Mutex<Receiver<Value>>,
+}
+
+impl Iterator for ReceiverIterator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.get_mut().unwrap().recv().ok()
+    }
+}
@@ -0,0 +1,267 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::*;
+
+// Helpers for testing commands. A harness comes preloaded with the built-in
+// commands, all wired up to a CaptureHostIo, and runs pipelines written as
+// source like `count | take 5 | sum`.
+pub struct TestHarness {
+    commands: CommandRegistry,
+    io: Arc<CaptureHostIo>,
+    warnings: Warnings,
+}
+
+pub struct ExampleFailure {
+    pub command: String,
+    pub example: Example,
+    pub actual: TestOutput,
+}
+
+pub struct TestOutput {
+    pub values: Vec<Value>,
+    pub stdout: String,
+    pub stderr: String,
+    pub errors: Vec<String>,
+    pub warnings: Vec<Warning>,
+}
+
+impl TestHarness {
+    pub fn new() -> Self {
+        Self::with_input(vec![])
+    }
+
+    // Lines given here are what `input` will read, in order
+    pub fn with_input(lines: Vec<String>) -> Self {
+        let io = Arc::new(CaptureHostIo::with_input(lines));
+        let warnings = Warnings::new();
+
+        let commands = Builtins {
+            io: io.clone(),
+            cwd: Arc::new(WorkingDir::from_process()),
+            warnings: warnings.clone(),
+            random: Arc::new(RandomSource::seeded(0)),
+            permissions: Arc::new(Permissions::all()),
+        }
+        .registry();
+
+        Self {
+            commands,
+            io,
+            warnings,
+        }
+    }
+
+    pub fn add(
+        &mut self,
+        signature: Signature,
+        command: impl PipelineElement + Send + Sync + 'static,
+    ) {
+        self.commands.insert(signature, command);
+    }
+
+    pub fn add_deprecated(
+        &mut self,
+        signature: Signature,
+        command: impl PipelineElement + Send + Sync + 'static,
+        replacement: &str,
+    ) {
+        self.commands
+            .insert_deprecated(signature, command, replacement);
+    }
+
+    pub fn run(&self, source: &str) -> TestOutput {
+        // Only report what this run printed, not earlier runs on the same harness
+        let stdout_start = self.io.stdout().len();
+        let stderr_start = self.io.stderr().len();
+
+        let mut errors = vec![];
+        let mut pipeline = vec![];
+
+        for stage in source.split('|') {
+            match self.parse_stage(stage) {
+                Ok(call) => pipeline.push(call),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        let values = if errors.is_empty() {
+            match build_pipeline_with_warnings(pipeline, &self.commands, &self.warnings) {
+                Ok(stream) => stream.collect(),
+                Err(err) => {
+                    errors.push(err.to_string());
+                    vec![]
+                }
+            }
+        } else {
+            vec![]
+        };
+
+        TestOutput {
+            values,
+            stdout: self.io.stdout()[stdout_start..].to_string(),
+            stderr: self.io.stderr()[stderr_start..].to_string(),
+            errors,
+            warnings: self.warnings.take(),
+        }
+    }
+
+    // Runs the examples of every registered command, returning those that didn't
+    // output what they say they do
+    pub fn check_examples(&self) -> Vec<ExampleFailure> {
+        let mut failures = vec![];
+
+        for signature in self.commands.signatures() {
+            for example in &signature.examples {
+                let actual = self.run(&example.source);
+
+                if !actual.errors.is_empty() || actual.values != example.expected {
+                    failures.push(ExampleFailure {
+                        command: signature.name.clone(),
+                        example: example.clone(),
+                        actual,
+                    });
+                }
+            }
+        }
+
+        failures
+    }
+
+    // Stages are a command name (which may be more than one word, like
+    // `str length`) followed by integer or string arguments
+    fn parse_stage(&self, stage: &str) -> Result<CallInfo, String> {
+        let words = split_words(stage)?;
+
+        let name_len = self
+            .commands
+            .longest_match(&words)
+            .ok_or_else(|| format!("unknown command in `{}`", stage.trim()))?;
+
+        let args = words[name_len..]
+            .iter()
+            .map(|word| match word.parse::<i64>() {
+                Ok(x) => Value::SmallInt(x),
+                Err(_) => Value::String(word.clone()),
+            })
+            .collect();
+
+        Ok(CallInfo {
+            name: words[..name_len].join(" "),
+            args,
+        })
+    }
+}
+
+impl Default for TestHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Splits on whitespace, keeping double-quoted words together (without the quotes)
+fn split_words(stage: &str) -> Result<Vec<String>, String> {
+    let mut words = vec![];
+    let mut chars = stage.trim().chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+
+        let mut word = String::new();
+        if c == '"' {
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some(c) => word.push(c),
+                    None => return Err(format!("unclosed string in `{}`", stage.trim())),
+                }
+            }
+        } else {
+            word.push(c);
+            while let Some(c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                word.push(*c);
+                chars.next();
+            }
+        }
+        words.push(word);
+    }
+
+    Ok(words)
+}
+
+impl TestOutput {
+    // A canonical text form of the output, stable across runs, for comparing
+    // against stored snapshots
+    pub fn to_snapshot(&self) -> String {
+        let mut snapshot = String::new();
+
+        for value in &self.values {
+            let line = match value {
+                Value::SmallInt(x) => format!("int {}", x),
+                Value::BigInt(x) => format!("bigint {}", x),
+                Value::String(s) => format!("string {:?}", s),
+                x => format!("other {:?}", x),
+            };
+            snapshot.push_str(&line);
+            snapshot.push('\n');
+        }
+
+        for (name, text) in &[("stdout", &self.stdout), ("stderr", &self.stderr)] {
+            if !text.is_empty() {
+                snapshot.push_str(&format!("--- {}\n{}", name, text));
+                if !text.ends_with('\n') {
+                    snapshot.push('\n');
+                }
+            }
+        }
+
+        for error in &self.errors {
+            snapshot.push_str(&format!("--- error\n{}\n", error));
+        }
+
+        for warning in &self.warnings {
+            snapshot.push_str(&format!("--- warning\n{}\n", warning));
+        }
+
+        snapshot
+    }
+}
+
+// Compares the output against the snapshot stored in `<dir>/<name>.snap`. The
+// directory is the caller's, usually something like
+// `Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots")` in their own
+// crate. A missing or different snapshot fails, unless ENGINEP_UPDATE_SNAPSHOTS
+// is set, in which case the output is written out as the new snapshot so it can
+// be reviewed and committed.
+pub fn assert_snapshot(dir: &Path, name: &str, output: &TestOutput) {
+    let path = dir.join(format!("{}.snap", name));
+    let actual = output.to_snapshot();
+
+    if std::env::var_os("ENGINEP_UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    match std::fs::read_to_string(&path) {
+        Ok(expected) => {
+            if expected != actual {
+                panic!(
+                    "snapshot `{}` doesn't match\n--- expected\n{}--- actual\n{}",
+                    name, expected, actual
+                );
+            }
+        }
+        Err(_) => panic!(
+            "no snapshot `{}` at {}; run with ENGINEP_UPDATE_SNAPSHOTS=1 to record it\n--- actual\n{}",
+            name,
+            path.display(),
+            actual
+        ),
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::{Arc, Mutex};
+
+// Something worth telling the user about that doesn't stop the pipeline, like
+// running a deprecated command
+#[derive(Clone, Debug, PartialEq)]
+pub struct Warning {
+    // The pipeline stage the warning came from, if it came from one
+    pub stage: Option<usize>,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            stage: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at_stage(stage: usize, message: impl Into<String>) -> Self {
+        Self {
+            stage: Some(stage),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.stage {
+            Some(stage) => write!(f, "stage {}: {}", stage, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+// Where warnings collect while a pipeline runs. Clones share the same
+// warnings, so the pipeline builder and any commands that warn can each hold
+// one, and the host takes them once the pipeline is done.
+#[derive(Clone, Default)]
+pub struct Warnings {
+    warnings: Arc<Mutex<Vec<Warning>>>,
+}
+
+impl Warnings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, warning: Warning) {
+        self.warnings.lock().unwrap().push(warning);
+    }
+
+    // Reports arguments a command can't use, like a negative count, as coming
+    // from that command
+    pub fn arg_error(&self, command: &str, err: impl std::fmt::Display) {
+        self.push(Warning::new(format!("{}: {}", command, err)));
+    }
+
+    // Removes and returns the warnings collected so far
+    pub fn take(&self) -> Vec<Warning> {
+        std::mem::take(&mut *self.warnings.lock().unwrap())
+    }
+}
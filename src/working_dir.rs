@@ -0,0 +1,48 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// The directory relative paths are resolved against. This belongs to whoever
+// creates it rather than the process, so a host running several sessions can
+// give each its own, and `cd` in one doesn't move the others.
+pub struct WorkingDir {
+    path: Mutex<PathBuf>,
+}
+
+impl WorkingDir {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Mutex::new(path.into()),
+        }
+    }
+
+    // Starts from the process's current directory
+    pub fn from_process() -> Self {
+        Self::new(std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/")))
+    }
+
+    pub fn get(&self) -> PathBuf {
+        self.path.lock().unwrap().clone()
+    }
+
+    pub fn set(&self, path: impl Into<PathBuf>) {
+        *self.path.lock().unwrap() = path.into();
+    }
+
+    // The path as it's meant from here: a leading `~` is the home directory, and
+    // relative paths are joined onto the working directory
+    pub fn resolve(&self, path: impl AsRef<Path>) -> PathBuf {
+        let path = path.as_ref();
+
+        if let (Ok(rest), Some(home)) = (path.strip_prefix("~"), home_dir()) {
+            return home.join(rest);
+        }
+
+        self.get().join(path)
+    }
+}
+
+pub(crate) fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use enginep::*;
+
+fn registry() -> CommandRegistry {
+    let mut registry = CommandRegistry::new();
+    registry.insert(CountCommand::signature(), CountCommand);
+    registry.insert(ContainsCommand::signature(), ContainsCommand);
+    registry.insert(StrLengthCommand::signature(), StrLengthCommand);
+    registry.insert(
+        PrintCommand::signature(),
+        PrintCommand::new(Arc::new(CaptureHostIo::new())),
+    );
+    registry.insert(
+        OpenCommand::signature(),
+        OpenCommand::new(Arc::new(WorkingDir::from_process())),
+    );
+    registry
+}
+
+fn texts(completions: Vec<Completion>) -> Vec<String> {
+    completions.into_iter().map(|x| x.text).collect()
+}
+
+#[test]
+fn command_names() {
+    let cwd = WorkingDir::from_process();
+
+    assert_eq!(
+        complete_at("cou", 3, &registry(), &cwd),
+        vec![Completion {
+            span: 0..3,
+            text: "count".into(),
+        }]
+    );
+    assert_eq!(
+        texts(complete_at("co", 2, &registry(), &cwd)),
+        vec!["contains", "count"]
+    );
+
+    // Only the stage being typed counts, and only up to the offset
+    assert_eq!(
+        complete_at("count | st | count", 10, &registry(), &cwd),
+        vec![Completion {
+            span: 8..10,
+            text: "str".into(),
+        }]
+    );
+    assert_eq!(
+        texts(complete_at("str le", 6, &registry(), &cwd)),
+        vec!["length"]
+    );
+}
+
+#[test]
+fn flags() {
+    let cwd = WorkingDir::from_process();
+
+    assert_eq!(
+        texts(complete_at("print --st", 10, &registry(), &cwd)),
+        vec!["--stderr"]
+    );
+}
+
+#[test]
+fn paths_from_the_working_directory() {
+    let dir = std::env::temp_dir().join("enginep-completion");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("folder")).unwrap();
+    std::fs::write(dir.join("file.txt"), "").unwrap();
+
+    let cwd = WorkingDir::new(&dir);
+
+    assert_eq!(
+        texts(complete_at("open f", 6, &registry(), &cwd)),
+        vec!["file.txt", "folder/"]
+    );
+    assert_eq!(
+        texts(complete_at("open ./fo", 9, &registry(), &cwd)),
+        vec!["./folder/"]
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn input_completeness() {
+    for source in &[
+        "",
+        "count | take 5",
+        "echo \"a | b\"",
+        "echo 'it''s'",
+        "echo \"say \\\"hi\\\"\"",
+        "each { echo (1) [2] }",
+        "echo )",
+    ] {
+        assert!(is_input_complete(source), "{:?}", source);
+    }
+
+    for source in &[
+        "count |",
+        "count | ",
+        "echo \"unclosed",
+        "echo \"escaped \\\"",
+        "each {",
+        "each { echo [1 }",
+        "echo `tick",
+    ] {
+        assert!(!is_input_complete(source), "{:?}", source);
+    }
+}
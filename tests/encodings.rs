@@ -0,0 +1,129 @@
+use enginep::testing::TestHarness;
+use enginep::*;
+
+fn run(source: &str) -> Vec<Value> {
+    let output = TestHarness::new().run(source);
+    assert!(
+        output.errors.is_empty(),
+        "`{}`: {:?}",
+        source,
+        output.errors
+    );
+    output.values
+}
+
+fn strings(values: &[&str]) -> Vec<Value> {
+    values
+        .iter()
+        .map(|x| Value::String(x.to_string()))
+        .collect()
+}
+
+// RFC 1321, appendix A.5
+#[test]
+fn md5_test_vectors() {
+    let cases = [
+        ("", "d41d8cd98f00b204e9800998ecf8427e"),
+        ("a", "0cc175b9c0f1b6a831c399e269772661"),
+        ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+        ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+        (
+            "abcdefghijklmnopqrstuvwxyz",
+            "c3fcd3d76192e4007dfb496cca67e13b",
+        ),
+        (
+            "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+            "57edf4a22be3c955ac49da2e2107b67a",
+        ),
+    ];
+
+    for (input, digest) in &cases {
+        assert_eq!(
+            run(&format!("echo \"{}\" | hash md5", input)),
+            strings(&[digest]),
+            "md5 of {:?}",
+            input
+        );
+    }
+}
+
+// FIPS 180-2, appendix B
+#[test]
+fn sha256_test_vectors() {
+    let cases = [
+        (
+            "",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ),
+        (
+            "abc",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ),
+        (
+            "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+        ),
+    ];
+
+    for (input, digest) in &cases {
+        assert_eq!(
+            run(&format!("echo \"{}\" | hash sha256", input)),
+            strings(&[digest]),
+            "sha256 of {:?}",
+            input
+        );
+    }
+}
+
+// RFC 4648, section 10
+#[test]
+fn base64_test_vectors() {
+    let cases = [
+        ("", ""),
+        ("f", "Zg=="),
+        ("fo", "Zm8="),
+        ("foo", "Zm9v"),
+        ("foob", "Zm9vYg=="),
+        ("fooba", "Zm9vYmE="),
+        ("foobar", "Zm9vYmFy"),
+    ];
+
+    for (input, encoded) in &cases {
+        assert_eq!(
+            run(&format!("echo \"{}\" | encode base64", input)),
+            strings(&[encoded]),
+            "encoding {:?}",
+            input
+        );
+        assert_eq!(
+            run(&format!("echo \"{}\" | decode base64", encoded)),
+            strings(&[input]),
+            "decoding {:?}",
+            encoded
+        );
+    }
+}
+
+#[test]
+fn invalid_base64_is_dropped() {
+    for input in &["a", "Zg=", "Zg===", "Zh==", "Zm9=v", "Zm9v*"] {
+        assert_eq!(
+            run(&format!("echo \"{}\" | decode base64", input)),
+            vec![],
+            "decoding {:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn invalid_hex_is_dropped() {
+    for input in &["+f", "-1", "6", "6g", "+f+f"] {
+        assert_eq!(
+            run(&format!("echo \"{}\" | decode hex", input)),
+            vec![],
+            "decoding {:?}",
+            input
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use enginep::testing::TestHarness;
+
+#[test]
+fn command_examples_output_what_they_say() {
+    let failures: Vec<_> = TestHarness::new()
+        .check_examples()
+        .into_iter()
+        .map(|x| {
+            format!(
+                "{}: `{}`\n{}",
+                x.command,
+                x.example.source,
+                x.actual.to_snapshot()
+            )
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
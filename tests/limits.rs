@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use enginep::*;
+
+fn call(name: &str, args: Vec<Value>) -> CallInfo {
+    CallInfo {
+        name: name.into(),
+        args,
+    }
+}
+
+// Outputs nothing for a long while, then passes its input on
+struct Hang;
+
+impl PipelineElement for Hang {
+    fn start(&self, args: CommandArgs) -> ValueIterator {
+        Box::new(
+            args.input
+                .inspect(|_| std::thread::sleep(Duration::from_secs(2))),
+        )
+    }
+}
+
+fn registry() -> CommandRegistry {
+    let mut registry = CommandRegistry::new();
+    registry.insert(CountCommand::signature(), CountCommand);
+    registry.insert(WhereCommand::signature(), WhereCommand);
+    registry.insert(Signature::build("hang"), Hang);
+    registry
+}
+
+#[test]
+fn max_values() {
+    let limits = Limits {
+        max_values: Some(5),
+        ..Limits::default()
+    };
+
+    let result = run_pipeline_with_limits(vec![call("count", vec![])], &registry(), &limits);
+    assert_eq!(result, Err(RunError::Limit(LimitExceeded::Values(5))));
+}
+
+#[test]
+fn max_duration_without_output() {
+    let limits = Limits {
+        max_duration: Some(Duration::from_millis(50)),
+        ..Limits::default()
+    };
+
+    // Busy the whole time, but nothing ever gets through the where
+    let pipeline = vec![
+        call("count", vec![]),
+        call("where", vec![Value::SmallInt(i64::MAX)]),
+    ];
+    let result = run_pipeline_with_limits(pipeline, &registry(), &limits);
+    assert_eq!(
+        result,
+        Err(RunError::Limit(LimitExceeded::Duration(
+            Duration::from_millis(50)
+        )))
+    );
+}
+
+#[test]
+fn zero_duration() {
+    let limits = Limits {
+        max_duration: Some(Duration::ZERO),
+        ..Limits::default()
+    };
+
+    let pipeline = vec![call("count", vec![]), call("hang", vec![])];
+    let result = run_pipeline_with_limits(pipeline, &registry(), &limits);
+    assert_eq!(
+        result,
+        Err(RunError::Limit(LimitExceeded::Duration(Duration::ZERO)))
+    );
+}
+
+#[test]
+fn stall_blames_the_stage() {
+    let limits = Limits {
+        stall_timeout: Some(Duration::from_millis(50)),
+        ..Limits::default()
+    };
+
+    let pipeline = vec![call("count", vec![]), call("hang", vec![])];
+    let result = run_pipeline_with_limits(pipeline, &registry(), &limits);
+    assert_eq!(
+        result,
+        Err(RunError::Limit(LimitExceeded::Stalled {
+            stage: 1,
+            command: "hang".into(),
+            timeout: Duration::from_millis(50),
+        }))
+    );
+}
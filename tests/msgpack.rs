@@ -0,0 +1,68 @@
+#![cfg(feature = "msgpack")]
+
+use enginep::testing::TestHarness;
+use enginep::*;
+use num_bigint::BigInt;
+
+fn values() -> Vec<Value> {
+    vec![
+        Value::SmallInt(0),
+        Value::SmallInt(-33),
+        Value::SmallInt(300),
+        Value::SmallInt(i64::MIN),
+        Value::SmallInt(i64::MAX),
+        Value::BigInt(BigInt::from(u64::MAX) * 1000),
+        Value::BigInt(-BigInt::from(u64::MAX) * 1000),
+        Value::String(String::new()),
+        Value::String("hello".into()),
+        Value::String("x".repeat(300)),
+    ]
+}
+
+#[test]
+fn codec_round_trips() {
+    for value in values() {
+        let bytes = to_msgpack(&value).unwrap();
+        assert_eq!(from_msgpack(&bytes), Ok((value, bytes.len())));
+    }
+}
+
+#[test]
+fn commands_round_trip() {
+    let mut h = TestHarness::new();
+
+    // A source for values the parser can't write, like BigInts
+    struct Values;
+
+    impl PipelineElement for Values {
+        fn start(&self, _: CommandArgs) -> ValueIterator {
+            Box::new(values().into_iter())
+        }
+    }
+    h.add(Signature::build("values"), Values);
+
+    let output = h.run("values | to msgpack | from msgpack");
+    assert!(output.errors.is_empty());
+    assert_eq!(output.values, values());
+}
+
+#[test]
+fn several_values_in_one_string() {
+    let output = TestHarness::new().run("echo 01a26869cd012c | from msgpack");
+
+    assert_eq!(
+        output.values,
+        vec![
+            Value::SmallInt(1),
+            Value::String("hi".into()),
+            Value::SmallInt(300),
+        ]
+    );
+}
+
+#[test]
+fn invalid_msgpack_is_dropped() {
+    let output = TestHarness::new().run("echo a2 c1 zz | from msgpack");
+
+    assert!(output.values.is_empty());
+}
@@ -0,0 +1,528 @@
+use std::sync::Arc;
+
+use enginep::testing::TestHarness;
+use enginep::*;
+
+#[test]
+fn missing_arguments_are_errors() {
+    let h = TestHarness::new();
+
+    for source in &["echo 1 2 | take", "echo 1 | where", "count | contains"] {
+        let output = h.run(source);
+        assert!(output.values.is_empty());
+        assert_eq!(output.errors.len(), 1, "`{}`", source);
+    }
+}
+
+#[test]
+fn cp_refuses_to_copy_a_directory_into_itself() {
+    let dir = std::env::temp_dir().join("enginep-cp-into-itself");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("a")).unwrap();
+
+    let output = TestHarness::new().run(&format!(
+        "cp -r \"{}\" \"{}\"",
+        dir.join("a").display(),
+        dir.join("a").join("sub").display()
+    ));
+
+    assert_eq!(output.warnings.len(), 1);
+    assert!(!dir.join("a").join("sub").exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn side_effects_wait_for_the_pipeline_to_run() {
+    let dir = std::env::temp_dir().join("enginep-side-effects");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let registry = Builtins {
+        io: Arc::new(CaptureHostIo::new()),
+        cwd: Arc::new(WorkingDir::from_process()),
+        warnings: Warnings::new(),
+        random: Arc::new(RandomSource::seeded(0)),
+        permissions: Arc::new(Permissions::all()),
+    }
+    .registry();
+    let rm = || {
+        vec![CallInfo {
+            name: "rm".into(),
+            args: vec![
+                Value::String("-r".into()),
+                Value::String(dir.display().to_string()),
+            ],
+        }]
+    };
+
+    let mut preview = PipelinePreview::new(10);
+    preview.set_stages(rm());
+    assert_eq!(preview.preview(&registry), &[vec![]]);
+
+    let dry_run: Vec<_> = build_dry_run_pipeline(rm(), &registry).unwrap().collect();
+    assert_eq!(dry_run.len(), 1);
+
+    let pipeline = build_pipeline(rm(), &registry).unwrap();
+    assert!(dir.exists());
+
+    pipeline.for_each(drop);
+    assert!(!dir.exists());
+}
+
+#[test]
+fn drop_more_than_there_are() {
+    let output = TestHarness::new().run("echo 1 2 | drop 9223372036854775807");
+
+    assert!(output.values.is_empty());
+    assert!(output.errors.is_empty());
+}
+
+#[test]
+fn seq_date_stops_at_the_last_year() {
+    let h = TestHarness::new();
+
+    let output = h.run("seq date 2020-01-01 -i 9223372036854775807 -n 3");
+    assert_eq!(output.values, vec![Value::String("2020-01-01".into())]);
+
+    let output = h.run("seq date 9999-12-31 -n 2");
+    assert_eq!(output.values, vec![Value::String("9999-12-31".into())]);
+}
+
+#[test]
+fn preview_keeps_earlier_stages_when_appending() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Source(Arc<AtomicUsize>);
+
+    impl PipelineElement for Source {
+        fn start(&self, args: CommandArgs) -> ValueIterator {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            CountCommand.start(args)
+        }
+    }
+
+    let starts = Arc::new(AtomicUsize::new(0));
+    let mut registry = CommandRegistry::new();
+    registry.insert(Signature::build("source"), Source(starts.clone()));
+    registry.insert(TakeCommand::signature(), TakeCommand);
+    registry.insert(WhereCommand::signature(), WhereCommand);
+
+    let call = |name: &str, args: Vec<i64>| CallInfo {
+        name: name.into(),
+        args: args.into_iter().map(Value::SmallInt).collect(),
+    };
+    let ints = |values: &[i64]| {
+        values
+            .iter()
+            .copied()
+            .map(Value::SmallInt)
+            .collect::<Vec<_>>()
+    };
+
+    let mut preview = PipelinePreview::new(3);
+    preview.set_stages(vec![call("source", vec![])]);
+    assert_eq!(preview.preview(&registry), &[ints(&[0, 1, 2])]);
+
+    preview.set_stages(vec![call("source", vec![]), call("where", vec![5])]);
+    assert_eq!(
+        preview.preview(&registry),
+        &[ints(&[0, 1, 2]), ints(&[6, 7, 8])]
+    );
+    assert_eq!(starts.load(Ordering::SeqCst), 1);
+
+    // A take cuts the source's preview short, so removing it means a fresh run
+    preview.set_stages(vec![call("source", vec![]), call("take", vec![1])]);
+    assert_eq!(preview.preview(&registry), &[ints(&[0]), ints(&[0])]);
+
+    preview.set_stages(vec![call("source", vec![])]);
+    assert_eq!(preview.preview(&registry), &[ints(&[0, 1, 2])]);
+}
+
+#[test]
+fn bits_take_bigints_that_fit() {
+    let output = TestHarness::new().run("echo hello | str length | bits and 1");
+
+    assert_eq!(output.values, vec![Value::SmallInt(1)]);
+}
+
+#[test]
+fn preview_leaves_the_terminal_alone() {
+    let io = Arc::new(CaptureHostIo::with_input(vec!["typed".into()]));
+    let registry = Builtins {
+        io: io.clone(),
+        cwd: Arc::new(WorkingDir::from_process()),
+        warnings: Warnings::new(),
+        random: Arc::new(RandomSource::seeded(0)),
+        permissions: Arc::new(Permissions::all()),
+    }
+    .registry();
+    let call = |name: &str| CallInfo {
+        name: name.into(),
+        args: vec![],
+    };
+
+    let mut preview = PipelinePreview::new(10);
+    preview.set_stages(vec![call("input"), call("print")]);
+    assert_eq!(preview.preview(&registry), &[vec![], vec![]]);
+
+    preview.set_stages(vec![call("count"), call("print")]);
+    preview.preview(&registry);
+
+    assert_eq!(io.stdout(), "");
+
+    // The line is still there for when the pipeline really runs
+    let output: Vec<_> = build_pipeline(vec![call("input")], &registry)
+        .unwrap()
+        .collect();
+    assert_eq!(output, vec![Value::String("typed".into())]);
+}
+
+#[test]
+fn clip_copy_only_copies_once() {
+    let io = Arc::new(CaptureHostIo::new());
+    let clip_copy = ClipCopyCommand::new(io.clone());
+
+    let mut stream = clip_copy.start(CommandArgs {
+        input: Box::new(vec![Value::String("a".into())].into_iter()),
+        args: vec![],
+        state: State,
+    });
+
+    assert_eq!(stream.next(), None);
+    assert_eq!(io.get_clipboard(), Some("a".into()));
+
+    assert_eq!(stream.next(), None);
+    assert_eq!(io.get_clipboard(), Some("a".into()));
+}
+
+#[test]
+fn unusable_arguments_are_warned_about() {
+    let h = TestHarness::new();
+
+    for source in &[
+        "echo 1 2 | drop -1",
+        "du -d -1",
+        "echo a | split row \"\"",
+        "seq date 2020-02-30",
+        "echo 1 | bits shl -1",
+        "char nonsense",
+        "random int 5 1",
+    ] {
+        let output = h.run(source);
+        assert!(output.values.is_empty(), "`{}`", source);
+        assert!(output.errors.is_empty(), "`{}`", source);
+        assert_eq!(output.warnings.len(), 1, "`{}`", source);
+    }
+}
+
+#[test]
+fn random_bool_outputs_both_ints() {
+    let h = TestHarness::new();
+
+    let mut seen = vec![];
+    for _ in 0..64 {
+        seen.extend(h.run("random bool").values);
+    }
+
+    assert!(seen.contains(&Value::SmallInt(0)));
+    assert!(seen.contains(&Value::SmallInt(1)));
+    assert!(seen
+        .iter()
+        .all(|x| *x == Value::SmallInt(0) || *x == Value::SmallInt(1)));
+}
+
+#[test]
+fn bits_shl_drops_what_would_overflow() {
+    let h = TestHarness::new();
+
+    let output = h.run("echo 4611686018427387904 -3 | bits shl 1");
+    assert_eq!(output.values, vec![Value::SmallInt(-6)]);
+
+    let output = h.run("echo 1 | bits shl 63");
+    assert!(output.values.is_empty());
+}
+
+#[test]
+fn du_outputs_sizes_as_ints() {
+    let dir = std::env::temp_dir().join("enginep-du");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("sub")).unwrap();
+    std::fs::write(dir.join("sub").join("file"), "hello").unwrap();
+
+    let h = TestHarness::new();
+
+    let file = dir.join("sub").join("file").display().to_string();
+    let output = h.run(&format!("du \"{}\"", file));
+    assert_eq!(output.values, vec![Value::SmallInt(5), Value::String(file)]);
+
+    let output = h.run(&format!("du -d 0 \"{}\"", dir.display()));
+    assert!(
+        matches!(output.values[..], [Value::SmallInt(size), Value::String(ref path)]
+        if size >= 5 && *path == dir.display().to_string())
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn short_flags_spelling_numbers_are_flags() {
+    let signature = Signature::build("flags")
+        .switch("ignore", Some('i'), "")
+        .switch("no", Some('n'), "")
+        .switch("force", Some('f'), "")
+        .switch("all", Some('a'), "")
+        .rest("rest", ArgType::Any, "");
+    let strings = |args: &[&str]| {
+        args.iter()
+            .map(|x| Value::String(x.to_string()))
+            .collect::<Vec<_>>()
+    };
+
+    let evaluated = EvaluatedArgs::new(&signature, strings(&["-inf"])).unwrap();
+    assert!(evaluated.has("ignore") && evaluated.has("no") && evaluated.has("force"));
+
+    let evaluated = EvaluatedArgs::new(&signature, strings(&["-nan"])).unwrap();
+    assert!(evaluated.has("no") && evaluated.has("all") && !evaluated.has("ignore"));
+    assert!(evaluated.rest().is_empty());
+
+    let evaluated = EvaluatedArgs::new(&signature, strings(&["-1.5", "-.5", "-"])).unwrap();
+    assert_eq!(evaluated.rest(), &strings(&["-1.5", "-.5", "-"])[..]);
+}
+
+#[test]
+fn denied_commands_warn() {
+    let dir = std::env::temp_dir().join("enginep-denied");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let warnings = Warnings::new();
+    let registry = Builtins {
+        io: Arc::new(CaptureHostIo::new()),
+        cwd: Arc::new(WorkingDir::from_process()),
+        warnings: warnings.clone(),
+        random: Arc::new(RandomSource::seeded(0)),
+        permissions: Arc::new(Permissions::all().deny(Capability::Filesystem)),
+    }
+    .registry();
+    let rm = CallInfo {
+        name: "rm".into(),
+        args: vec![
+            Value::String("-r".into()),
+            Value::String(dir.display().to_string()),
+        ],
+    };
+
+    let output: Vec<_> = build_pipeline(vec![rm], &registry).unwrap().collect();
+    assert!(output.is_empty());
+    assert!(dir.exists());
+    assert_eq!(
+        warnings.take(),
+        vec![Warning::new("rm: needs filesystem access, which is denied")]
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn timeit_checks_its_runs() {
+    let mut h = TestHarness::new();
+    h.add(
+        TimeitCommand::signature(),
+        TimeitCommand::new(Warnings::new(), EchoCommand),
+    );
+
+    let output = h.run("timeit 3 a b");
+    assert_eq!(output.values.len(), 3);
+    assert!(output
+        .values
+        .iter()
+        .all(|x| matches!(x, Value::SmallInt(ns) if *ns >= 0)));
+
+    let output = h.run("timeit");
+    assert!(output.values.is_empty());
+    assert_eq!(output.errors.len(), 1);
+}
+
+#[test]
+fn every_skips_missed_runs() {
+    use std::time::{Duration, Instant};
+
+    let warnings = Warnings::new();
+    let every = EveryCommand::new(warnings.clone(), EchoCommand);
+    let start = |interval: i64| {
+        every.start(CommandArgs {
+            input: Box::new(std::iter::empty()),
+            args: vec![Value::SmallInt(interval), Value::String("tick".into())],
+            state: State,
+        })
+    };
+
+    let mut ticks = start(50);
+    ticks.next();
+    std::thread::sleep(Duration::from_millis(300));
+
+    // One late run right away, then back to the interval
+    ticks.next();
+    let resumed = Instant::now();
+    ticks.next();
+    ticks.next();
+    assert!(resumed.elapsed() >= Duration::from_millis(90));
+
+    assert_eq!(start(0).next(), None);
+    assert_eq!(warnings.take().len(), 1);
+}
+
+#[test]
+fn debugger_sees_each_stage() {
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl DebuggerHook for Recorder {
+        fn on_enter(&self, stage: usize, call: &CallInfo) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("enter {} {}", stage, call.name));
+        }
+
+        fn on_value(&self, stage: usize, value: &Value) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("value {} {:?}", stage, value));
+        }
+
+        fn on_leave(&self, stage: usize) {
+            self.0.lock().unwrap().push(format!("leave {}", stage));
+        }
+
+        fn on_breakpoint(&self, label: &str, value: &Value) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("break {} {:?}", label, value));
+        }
+    }
+
+    let recorder = Arc::new(Recorder::default());
+    let mut registry = CommandRegistry::new();
+    registry.insert(CountCommand::signature(), CountCommand);
+    registry.insert(TakeCommand::signature(), TakeCommand);
+    registry.insert(
+        BreakpointCommand::signature(),
+        BreakpointCommand::new(recorder.clone(), Warnings::new()),
+    );
+
+    let pipeline = vec![
+        CallInfo {
+            name: "count".into(),
+            args: vec![],
+        },
+        CallInfo {
+            name: "take".into(),
+            args: vec![Value::SmallInt(2)],
+        },
+        CallInfo {
+            name: "breakpoint".into(),
+            args: vec![Value::String("here".into())],
+        },
+    ];
+    let output: Vec<_> = build_debug_pipeline(pipeline, &registry, recorder.clone())
+        .unwrap()
+        .collect();
+
+    assert_eq!(output, vec![Value::SmallInt(0), Value::SmallInt(1)]);
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        vec![
+            "enter 0 count",
+            "enter 1 take",
+            "enter 2 breakpoint",
+            "value 0 SmallInt(0)",
+            "value 1 SmallInt(0)",
+            "break here SmallInt(0)",
+            "value 2 SmallInt(0)",
+            "value 0 SmallInt(1)",
+            "value 1 SmallInt(1)",
+            "break here SmallInt(1)",
+            "value 2 SmallInt(1)",
+            // take stops pulling, so count never finishes
+            "leave 1",
+            "leave 2",
+        ]
+    );
+}
+
+#[test]
+fn interleave_merges_finite_streams() {
+    struct Tens;
+
+    impl PipelineElement for Tens {
+        fn start(&self, _: CommandArgs) -> ValueIterator {
+            Box::new((10..13).map(Value::SmallInt))
+        }
+    }
+
+    let interleave = InterleaveCommand::new(vec![Box::new(EchoCommand), Box::new(Tens)]);
+    let output: Vec<_> = interleave
+        .start(CommandArgs {
+            input: Box::new((0..3).map(Value::SmallInt)),
+            args: vec![Value::String("a".into()), Value::String("b".into())],
+            state: State,
+        })
+        .collect();
+
+    // Every value arrives, and each stream's own values stay in order
+    let from = |wanted: &[Value]| {
+        output
+            .iter()
+            .filter(|x| wanted.contains(x))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    let input: Vec<_> = (0..3).map(Value::SmallInt).collect();
+    let echo = vec![Value::String("a".into()), Value::String("b".into())];
+    let tens: Vec<_> = (10..13).map(Value::SmallInt).collect();
+
+    assert_eq!(output.len(), 8);
+    assert_eq!(from(&input), input);
+    assert_eq!(from(&echo), echo);
+    assert_eq!(from(&tens), tens);
+}
+
+#[test]
+fn tee_forwards_and_copies_the_same_values() {
+    use std::sync::Mutex;
+
+    // Keeps a copy of whatever it's given, with its arguments in front
+    struct Keep(Arc<Mutex<Vec<Value>>>);
+
+    impl PipelineElement for Keep {
+        fn start(&self, args: CommandArgs) -> ValueIterator {
+            let kept = self.0.clone();
+            kept.lock().unwrap().extend(args.args);
+            Box::new(
+                args.input
+                    .inspect(move |x| kept.lock().unwrap().push(x.clone())),
+            )
+        }
+    }
+
+    let kept = Arc::new(Mutex::new(vec![]));
+    let tee = TeeCommand::new(Box::new(Keep(kept.clone())));
+
+    let values: Vec<_> = (0..2000).map(Value::SmallInt).collect();
+    let output: Vec<_> = tee
+        .start(CommandArgs {
+            input: Box::new(values.clone().into_iter()),
+            args: vec![Value::String("log".into())],
+            state: State,
+        })
+        .collect();
+
+    assert_eq!(output, values);
+
+    let mut expected = vec![Value::String("log".into())];
+    expected.extend(values);
+    assert_eq!(*kept.lock().unwrap(), expected);
+}